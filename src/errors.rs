@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt;
+use math::variables::Name;
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SolveError<N> {
+    /// No point satisfies every constraint (Phase I could not drive "W" to zero).
+    Infeasible,
+    /// The objective can be improved without limit along the given ray, listed as the change
+    /// in each variable per unit increase of the entering variable.
+    Unbounded(Vec<(Name, N)>),
+    /// The simplex ran out of pivots before it found a feasible point.
+    IterationLimit,
+    /// The simplex ran out of time before it found a feasible point.
+    TimeLimit,
+    /// The solve was cancelled before it found a feasible point.
+    Cancelled,
+    /// The function or the constraints are not in a form the solver understands.
+    MalformedModel(String),
+}
+
+impl<N> fmt::Display for SolveError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SolveError::Infeasible => write!(f, "problem has no feasible solution"),
+            SolveError::Unbounded(_) => write!(f, "problem is unbounded"),
+            SolveError::IterationLimit => write!(f, "iteration limit reached"),
+            SolveError::TimeLimit => write!(f, "time limit reached"),
+            SolveError::Cancelled => write!(f, "solve cancelled"),
+            SolveError::MalformedModel(ref reason) => write!(f, "malformed model: {}", reason),
+        }
+    }
+}
+
+impl<N: fmt::Debug> Error for SolveError<N> {}
+
+/// Why a model file could not be read and where in it, counting lines and columns from one.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: String) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
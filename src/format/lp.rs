@@ -0,0 +1,570 @@
+use Num;
+use math::variables::{AbstVar, new_var, new_const, is_reserved_name};
+use math::relationships::Relationship;
+use math::expressions::Expression;
+use objective::problems::ProblemType;
+use objective::functions::Function;
+use objective::constraints::{Constraint, SystemOfConstraints, new_reg_con};
+use errors::{ParseError, SolveError};
+use format::rows::{get_model, get_bounds_of, new_bounds, get_reserved_name_reason};
+
+// The characters besides letters and digits that may appear in a name.
+const NAME_SYMBOLS: &str = "!\"#$%&()/,.;?@_`'{}|~";
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Section {
+    // Whether the objective is to be maximised.
+    Objective(bool),
+    Constraints,
+    Bounds,
+    Integers,
+    End,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum Token {
+    Number(String),
+    Name(String),
+    Colon,
+    Plus,
+    Minus,
+    Sense(Relationship),
+}
+
+#[derive(Debug, Clone)]
+struct Located {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+// A section header and everything written under it.
+struct Block {
+    section: Section,
+    line: usize,
+    column: usize,
+    tokens: Vec<Located>,
+}
+
+/// Reads a model in the CPLEX LP format. Bounds become `Constraint::Bounded`, but every
+/// variable needs a finite lower bound, so free variables are turned away along with integer
+/// ones.
+pub fn parse_lp<N: Num>(text: &str) -> Result<(Function<N>, SystemOfConstraints<N>), ParseError> {
+    let blocks = get_blocks(text)?;
+    let mut blocks = blocks.iter();
+    let function = match blocks.next() {
+        Some(&Block { section: Section::Objective(max), ref tokens, .. }) => {
+            parse_objective(tokens, if max { ProblemType::MAX } else { ProblemType::MIN })?
+        }
+        Some(block) => {
+            return Err(ParseError::new(block.line,
+                                       block.column,
+                                       "expected \"Maximize\" or \"Minimize\" first.".to_string()))
+        }
+        None => return Err(ParseError::new(1, 1, "expected an objective.".to_string())),
+    };
+    let mut constraints = Vec::new();
+    for block in blocks {
+        match block.section {
+            Section::Objective(_) => {
+                let reason = "a model has only one objective.".to_string();
+                return Err(ParseError::new(block.line, block.column, reason));
+            }
+            Section::Constraints => constraints.extend(parse_constraints(&block.tokens)?),
+            Section::Bounds => constraints.extend(parse_bounds(&block.tokens)?),
+            Section::Integers => {
+                if let Some(located) = block.tokens.first() {
+                    let reason = "integer variables are not supported.".to_string();
+                    return Err(error_at(located, reason));
+                }
+            }
+            Section::End => break,
+        }
+    }
+    Ok((function, SystemOfConstraints::new(constraints)))
+}
+
+/// Writes a model in the CPLEX LP format, the same before or after it has been solved.
+pub fn write_lp<N: Num>(function: &Function<N>,
+                        constraints: &SystemOfConstraints<N>)
+                        -> Result<String, SolveError<N>> {
+    let model = get_model(function, constraints, is_name)?;
+    let mut text = String::new();
+    text.push_str(match *function.p_type() {
+        ProblemType::MAX => "Maximize\n",
+        ProblemType::MIN => "Minimize\n",
+    });
+    let name = check_name(&function.name().to_string())?;
+    text.push_str(&format!(" {}: {}\n", name, write_terms(&model.objective)?));
+    text.push_str("Subject To\n");
+    for row in model.rows {
+        let rel = match row.rel {
+            Relationship::LEQ => "<=",
+            Relationship::GEQ => ">=",
+            Relationship::EQ => "=",
+        };
+        text.push_str(&format!(" {}: {} {} {}\n",
+                               row.label,
+                               write_terms(&row.terms)?,
+                               rel,
+                               row.rhs.to_decimal()));
+    }
+    if !model.bounds.is_empty() {
+        text.push_str("Bounds\n");
+        for (name, lower, upper) in model.bounds {
+            let name = check_name(&name)?;
+            text.push_str(&match upper {
+                Some(ref upper) if *upper == lower => {
+                    format!(" {} = {}\n", name, upper.to_decimal())
+                }
+                Some(upper) => {
+                    format!(" {} <= {} <= {}\n", lower.to_decimal(), name, upper.to_decimal())
+                }
+                None => format!(" {} >= {}\n", name, lower.to_decimal()),
+            });
+        }
+    }
+    text.push_str("End\n");
+    Ok(text)
+}
+
+fn get_blocks(text: &str) -> Result<Vec<Block>, ParseError> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        // Comments run from a backslash to the end of the line.
+        let line = line.split('\\').next().unwrap_or("");
+        let (content, start) = match get_header(line) {
+            Some((section, start)) => {
+                let column = line.chars().take_while(|c| c.is_whitespace()).count() + 1;
+                blocks.push(Block {
+                    section,
+                    line: i + 1,
+                    column,
+                    tokens: Vec::new(),
+                });
+                (&line[start..], line[..start].chars().count())
+            }
+            None => (line, 0),
+        };
+        let tokens = tokenise(content, i + 1, start)?;
+        match blocks.last_mut() {
+            Some(block) => block.tokens.extend(tokens),
+            None => {
+                if let Some(located) = tokens.first() {
+                    let reason = "expected \"Maximize\" or \"Minimize\" first.".to_string();
+                    return Err(error_at(located, reason));
+                }
+            }
+        }
+    }
+    Ok(blocks)
+}
+
+// The section a line starts, if any, and where the rest of the line begins.
+fn get_header(line: &str) -> Option<(Section, usize)> {
+    let trimmed = line.trim_start();
+    let offset = line.len() - trimmed.len();
+    let lower = trimmed.to_lowercase();
+    let words = lower.split_whitespace().collect::<Vec<&str>>();
+    let first = *words.first()?;
+    let (section, num_words) = match first {
+        "maximize" | "maximise" | "maximum" | "max" => (Section::Objective(true), 1),
+        "minimize" | "minimise" | "minimum" | "min" => (Section::Objective(false), 1),
+        "subject" | "such" if words.len() > 1 && (words[1] == "to" || words[1] == "that") => {
+            (Section::Constraints, 2)
+        }
+        "st" | "s.t." | "st." => (Section::Constraints, 1),
+        "bounds" | "bound" => (Section::Bounds, 1),
+        "general" | "generals" | "gen" | "integer" | "integers" | "binary" | "binaries" |
+        "bin" => (Section::Integers, 1),
+        "end" => (Section::End, 1),
+        _ => return None,
+    };
+    // Step over the header words to where the rest of the line starts.
+    let mut end = 0;
+    for _ in 0..num_words {
+        let rest = &trimmed[end..];
+        let word_start = end + (rest.len() - rest.trim_start().len());
+        let word_len = trimmed[word_start..]
+            .find(char::is_whitespace)
+            .unwrap_or(trimmed.len() - word_start);
+        end = word_start + word_len;
+    }
+    Some((section, offset + end))
+}
+
+fn tokenise(content: &str, line: usize, start: usize) -> Result<Vec<Located>, ParseError> {
+    let chars = content.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = start + i + 1;
+        let next = chars.get(i + 1).cloned();
+        let begins_number = c.is_ascii_digit() ||
+                            (c == '.' && next.is_some_and(|next| next.is_ascii_digit()));
+        let token = if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if begins_number {
+            let begin = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut end = i + 1;
+                if end < chars.len() && (chars[end] == '+' || chars[end] == '-') {
+                    end += 1;
+                }
+                if end < chars.len() && chars[end].is_ascii_digit() {
+                    i = end;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            Token::Number(chars[begin..i].iter().collect())
+        } else if is_name_char(c) {
+            let begin = i;
+            while i < chars.len() && (is_name_char(chars[i]) || chars[i].is_ascii_digit()) {
+                i += 1;
+            }
+            let name: String = chars[begin..i].iter().collect();
+            if is_reserved_name(&name) {
+                return Err(ParseError::new(line, column, get_reserved_name_reason(&name)));
+            }
+            Token::Name(name)
+        } else {
+            i += 1;
+            match c {
+                ':' => Token::Colon,
+                '+' => Token::Plus,
+                '-' => Token::Minus,
+                '<' | '>' | '=' => {
+                    // Take "<", "<=" and "=<" all to mean at most, and the same the other way.
+                    let mut sense = c;
+                    if next == Some('=') || (c == '=' && (next == Some('<') || next == Some('>'))) {
+                        i += 1;
+                        if c == '=' {
+                            sense = next.unwrap_or(c);
+                        }
+                    }
+                    Token::Sense(match sense {
+                        '<' => Relationship::LEQ,
+                        '>' => Relationship::GEQ,
+                        _ => Relationship::EQ,
+                    })
+                }
+                _ => {
+                    let reason = format!("unexpected character '{}'.", c);
+                    return Err(ParseError::new(line, column, reason));
+                }
+            }
+        };
+        tokens.push(Located {
+            token,
+            line,
+            column,
+        });
+    }
+    Ok(tokens)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || NAME_SYMBOLS.contains(c)
+}
+
+fn is_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| is_name_char(c) && c != '.') &&
+    name.chars().all(|c| is_name_char(c) || c.is_ascii_digit())
+}
+
+fn check_name<N>(name: &str) -> Result<String, SolveError<N>> {
+    if is_name(name) {
+        Ok(name.to_string())
+    } else {
+        let reason = format!("\"{}\" is not a name LP files can hold.", name);
+        Err(SolveError::MalformedModel(reason))
+    }
+}
+
+fn error_at(located: &Located, message: String) -> ParseError {
+    ParseError::new(located.line, located.column, message)
+}
+
+fn error_after(tokens: &[Located], message: String) -> ParseError {
+    match tokens.last() {
+        Some(located) => error_at(located, message),
+        None => ParseError::new(1, 1, message),
+    }
+}
+
+// A name followed by a colon at the start of an objective or constraint.
+fn parse_label(tokens: &[Located], pos: &mut usize) -> Option<String> {
+    match (tokens.get(*pos).map(|l| &l.token), tokens.get(*pos + 1).map(|l| &l.token)) {
+        (Some(Token::Name(name)), Some(&Token::Colon)) => {
+            *pos += 2;
+            Some(name.clone())
+        }
+        _ => None,
+    }
+}
+
+fn parse_number<N: Num>(located: &Located, text: &str) -> Result<N, ParseError> {
+    N::from_decimal(text).ok_or_else(|| error_at(located, format!("\"{}\" is not a number.", text)))
+}
+
+// Terms up to whatever can not be part of them. Constants go into the second value.
+fn parse_terms<N: Num>(tokens: &[Located],
+                       pos: &mut usize)
+                       -> Result<(Vec<AbstVar<N>>, N), ParseError> {
+    let mut terms = Vec::new();
+    let mut constant = N::zero();
+    let mut first = true;
+    loop {
+        let start = *pos;
+        let mut sign = N::one();
+        let mut signed = false;
+        while let Some(located) = tokens.get(*pos) {
+            match located.token {
+                Token::Plus => {}
+                Token::Minus => sign = -sign,
+                _ => break,
+            }
+            signed = true;
+            *pos += 1;
+        }
+        let coefficient = match tokens.get(*pos) {
+            Some(&Located { token: Token::Number(ref text), .. }) => {
+                let number = parse_number(&tokens[*pos], text)?;
+                *pos += 1;
+                Some(number)
+            }
+            _ => None,
+        };
+        let is_label = tokens.get(*pos + 1).is_some_and(|located| located.token == Token::Colon);
+        match tokens.get(*pos) {
+            Some(&Located { token: Token::Name(ref name), .. }) if !is_label => {
+                if !first && !signed {
+                    return Err(error_at(&tokens[start], "expected '+' or '-'.".to_string()));
+                }
+                *pos += 1;
+                terms.push(new_var(name, sign * coefficient.unwrap_or(N::one())));
+            }
+            _ => {
+                match coefficient {
+                    Some(number) if first || signed => constant += sign * number,
+                    Some(_) => {
+                        return Err(error_at(&tokens[start], "expected '+' or '-'.".to_string()))
+                    }
+                    None if signed => {
+                        let reason = "expected a number or a variable.".to_string();
+                        return Err(error_after(&tokens[..*pos], reason));
+                    }
+                    None => return Ok((terms, constant)),
+                }
+            }
+        }
+        first = false;
+    }
+}
+
+fn parse_objective<N: Num>(tokens: &[Located],
+                           p_type: ProblemType)
+                           -> Result<Function<N>, ParseError> {
+    let mut pos = 0;
+    let name = parse_label(tokens, &mut pos).unwrap_or_else(|| "obj".to_string());
+    let (terms, constant) = parse_terms::<N>(tokens, &mut pos)?;
+    if let Some(located) = tokens.get(pos) {
+        return Err(error_at(located, "expected '+' or '-'.".to_string()));
+    }
+    if constant != N::zero() {
+        let reason = "constants in the objective are not supported.".to_string();
+        return Err(error_after(tokens, reason));
+    }
+    let mut exp = Expression::new(vec![new_var(&name, N::one())], Relationship::EQ, Vec::new());
+    for term in terms {
+        exp.add_rhs(term);
+    }
+    Ok(Function::new(exp, p_type))
+}
+
+fn parse_constraints<N: Num>(tokens: &[Located]) -> Result<Vec<Constraint<N>>, ParseError> {
+    let mut constraints = Vec::new();
+    let mut pos = 0;
+    while pos < tokens.len() {
+        let start = pos;
+        let label = parse_label(tokens, &mut pos);
+        let (terms, constant) = parse_terms::<N>(tokens, &mut pos)?;
+        let rel = match tokens.get(pos) {
+            Some(&Located { token: Token::Sense(ref rel), .. }) => rel.clone(),
+            Some(located) => {
+                return Err(error_at(located, "expected '<=', '>=' or '='.".to_string()))
+            }
+            None => {
+                let reason = "expected '<=', '>=' or '='.".to_string();
+                return Err(error_after(tokens, reason));
+            }
+        };
+        if terms.is_empty() {
+            return Err(error_at(&tokens[start], "expected a variable.".to_string()));
+        }
+        pos += 1;
+        let rhs = parse_value::<N>(tokens, &mut pos)?
+            .ok_or_else(|| error_after(&tokens[..pos], "expected a finite number.".to_string()))?;
+        let label = label.unwrap_or_else(|| format!("c{}", constraints.len() + 1));
+        let mut exp = Expression::new(Vec::new(), rel, vec![new_const(&label, rhs - constant)]);
+        for term in terms {
+            exp.add_lhs(term);
+        }
+        constraints.push(new_reg_con(exp));
+    }
+    Ok(constraints)
+}
+
+// A signed number or infinity, which comes back as `None`.
+fn parse_value<N: Num>(tokens: &[Located], pos: &mut usize) -> Result<Option<N>, ParseError> {
+    let mut sign = N::one();
+    while let Some(located) = tokens.get(*pos) {
+        match located.token {
+            Token::Plus => {}
+            Token::Minus => sign = -sign,
+            _ => break,
+        }
+        *pos += 1;
+    }
+    match tokens.get(*pos) {
+        Some(&Located { token: Token::Number(ref text), .. }) => {
+            let number = parse_number::<N>(&tokens[*pos], text)?;
+            *pos += 1;
+            Ok(Some(sign * number))
+        }
+        Some(&Located { token: Token::Name(ref name), .. }) if is_infinity(name) => {
+            if sign < N::zero() {
+                Err(unbounded_below_error(&tokens[*pos]))
+            } else {
+                *pos += 1;
+                Ok(None)
+            }
+        }
+        Some(located) => Err(error_at(located, "expected a number.".to_string())),
+        None => Err(error_after(tokens, "expected a number.".to_string())),
+    }
+}
+
+fn is_infinity(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == "inf" || lower == "infinity"
+}
+
+fn unbounded_below_error(located: &Located) -> ParseError {
+    error_at(located,
+             "variables need a finite lower bound, so free ones are not supported.".to_string())
+}
+
+fn parse_bounds<N: Num>(tokens: &[Located]) -> Result<Vec<Constraint<N>>, ParseError> {
+    let mut bounds = Vec::new();
+    let mut pos = 0;
+    while pos < tokens.len() {
+        let start = pos;
+        let name = match tokens[pos].token {
+            Token::Name(ref name) if !is_infinity(name) => {
+                pos += 1;
+                name.clone()
+            }
+            _ => {
+                // "l <= x" or "l <= x <= u", the other way round to the rest.
+                let lower = parse_value::<N>(tokens, &mut pos)?;
+                let rel = parse_sense(tokens, &mut pos)?;
+                let name = match tokens.get(pos) {
+                    Some(&Located { token: Token::Name(ref name), .. }) => name.clone(),
+                    Some(located) => {
+                        return Err(error_at(located, "expected a variable.".to_string()))
+                    }
+                    None => return Err(error_after(tokens, "expected a variable.".to_string())),
+                };
+                pos += 1;
+                set_bound(&mut bounds, &name, flip(rel), lower, &tokens[start])?;
+                if let Some(&Located { token: Token::Sense(_), .. }) = tokens.get(pos) {
+                    let rel = parse_sense(tokens, &mut pos)?;
+                    let upper = parse_value::<N>(tokens, &mut pos)?;
+                    set_bound(&mut bounds, &name, rel, upper, &tokens[start])?;
+                }
+                continue;
+            }
+        };
+        if let Some(&Located { token: Token::Name(ref word), .. }) = tokens.get(pos) {
+            if word.to_lowercase() == "free" {
+                return Err(unbounded_below_error(&tokens[start]));
+            }
+        }
+        let rel = parse_sense(tokens, &mut pos)?;
+        let value = parse_value::<N>(tokens, &mut pos)?;
+        set_bound(&mut bounds, &name, rel, value, &tokens[start])?;
+    }
+    Ok(bounds.into_iter().map(|(name, lower, upper)| new_bounds(&name, lower, upper)).collect())
+}
+
+fn parse_sense(tokens: &[Located], pos: &mut usize) -> Result<Relationship, ParseError> {
+    match tokens.get(*pos) {
+        Some(&Located { token: Token::Sense(ref rel), .. }) => {
+            *pos += 1;
+            Ok(rel.clone())
+        }
+        Some(located) => Err(error_at(located, "expected '<=', '>=' or '='.".to_string())),
+        None => Err(error_after(tokens, "expected '<=', '>=' or '='.".to_string())),
+    }
+}
+
+fn flip(rel: Relationship) -> Relationship {
+    match rel {
+        Relationship::LEQ => Relationship::GEQ,
+        Relationship::GEQ => Relationship::LEQ,
+        Relationship::EQ => Relationship::EQ,
+    }
+}
+
+// Narrows down the bounds of a variable, with an infinite value as `None`.
+fn set_bound<N: Num>(bounds: &mut Vec<(String, N, Option<N>)>,
+                     name: &str,
+                     rel: Relationship,
+                     value: Option<N>,
+                     located: &Located)
+                     -> Result<(), ParseError> {
+    let bound = get_bounds_of(bounds, name);
+    match (rel, value) {
+        (Relationship::LEQ, upper) => bound.2 = upper,
+        (_, None) => return Err(error_at(located, "a lower bound must be finite.".to_string())),
+        (Relationship::GEQ, Some(lower)) => bound.1 = lower,
+        (Relationship::EQ, Some(value)) => {
+            bound.1 = value.clone();
+            bound.2 = Some(value);
+        }
+    }
+    Ok(())
+}
+
+fn write_terms<N: Num>(terms: &[(String, N)]) -> Result<String, SolveError<N>> {
+    let mut text = String::new();
+    for (i, (name, coefficient)) in terms.iter().enumerate() {
+        let name = check_name(name)?;
+        let (sign, magnitude) = if *coefficient < N::zero() {
+            ("-", -coefficient.clone())
+        } else {
+            ("+", coefficient.clone())
+        };
+        if i > 0 {
+            text.push(' ');
+        }
+        if i > 0 || sign == "-" {
+            text.push_str(sign);
+            text.push(' ');
+        }
+        if magnitude != N::one() {
+            text.push_str(&magnitude.to_decimal());
+            text.push(' ');
+        }
+        text.push_str(&name);
+    }
+    Ok(text)
+}
@@ -0,0 +1,128 @@
+pub mod lp;
+pub mod mps;
+mod rows;
+
+#[cfg(test)]
+mod tests {
+    use math::variables::{new_var, new_const};
+    use math::relationships::Relationship;
+    use math::expressions::Expression;
+    use objective::problems::ProblemType;
+    use objective::functions::Function;
+    use objective::constraints::{Constraint, new_reg_con, new_non_neg_con, new_stay_con,
+                                 SystemOfConstraints};
+    use format::lp::{parse_lp, write_lp};
+    use format::mps::{MpsFormat, parse_mps};
+
+    fn assert_regular(constraint: &Constraint<f64>, expected: Expression<f64>) {
+        match *constraint {
+            Constraint::Regular(ref exp) => {
+                assert_eq!(format!("{:?}", expected), format!("{:?}", exp))
+            }
+            _ => panic!("Unexpected variant in this program logic."),
+        }
+    }
+
+    fn assert_bounded(constraint: &Constraint<f64>, name: &str, lower: f64, upper: Option<f64>) {
+        match *constraint {
+            Constraint::Bounded(ref var, ref l, ref u) => {
+                assert_eq!((Some(name), lower, upper), (var.name().as_str(), *l, *u))
+            }
+            _ => panic!("Unexpected variant in this program logic."),
+        }
+    }
+
+    #[test]
+    fn can_parse_lp() {
+        let text = "\\ A comment\n\
+                    Maximize\n obj: 2 x + 3.5 y\n\
+                    Subject To\n c1: x + y <= 4\n x - y >= -2\n\
+                    Bounds\n x <= 3\n 1 <= y <= 5\n\
+                    End\n";
+        let (function, constraints) = parse_lp::<f64>(text).unwrap();
+        assert_eq!(ProblemType::MAX, *function.p_type());
+        assert_eq!("obj", function.name());
+        assert_eq!(&vec![new_var("x", 2.0), new_var("y", 3.5)], function.exp().rhs());
+
+        let system = constraints.system();
+        assert_eq!(4, system.len());
+        assert_regular(&system[0],
+                       Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                                       Relationship::LEQ,
+                                       vec![new_const("c1", 4.0)]));
+        assert_regular(&system[1],
+                       Expression::new(vec![new_var("x", 1.0), new_var("y", -1.0)],
+                                       Relationship::GEQ,
+                                       vec![new_const("c2", -2.0)]));
+        assert_bounded(&system[2], "x", 0.0, Some(3.0));
+        assert_bounded(&system[3], "y", 1.0, Some(5.0));
+    }
+
+    #[test]
+    fn can_report_where_lp_is_wrong() {
+        let error = parse_lp::<f64>("Minimize\n cost: x + y\nSubject To\n c1: x + y >= \n")
+            .err()
+            .unwrap();
+        assert_eq!((4, 12), (error.line(), error.column()));
+
+        let error = parse_lp::<f64>("Minimize\n x + y\nst\n x y >= 1\n").err().unwrap();
+        assert_eq!((4, 4), (error.line(), error.column()));
+
+        let error = parse_lp::<f64>("Minimize\n x\nBounds\n x free\n").err().unwrap();
+        assert_eq!((4, 2), (error.line(), error.column()));
+
+        let error = parse_lp::<f64>("Minimize\n x\nGeneral\n x\n").err().unwrap();
+        assert_eq!((4, 2), (error.line(), error.column()));
+
+        let error = parse_lp::<f64>(" x + y\n").err().unwrap();
+        assert_eq!((1, 2), (error.line(), error.column()));
+        assert_eq!("line 1, column 2: expected \"Maximize\" or \"Minimize\" first.",
+                   error.to_string());
+    }
+
+    #[test]
+    fn can_write_lp() {
+        let function = Function::new(Expression::new(vec![new_var("P", 1.0)],
+                                                     Relationship::EQ,
+                                                     vec![new_var("x", 2.5), new_var("y", -3.0)]),
+                                     ProblemType::MIN);
+        let constraints = SystemOfConstraints::new(vec![
+            new_reg_con(Expression::new(vec![new_var("x", 3.0), new_var("y", 1.0)],
+                                        Relationship::LEQ,
+                                        vec![new_const("con1", 90.0)])),
+            new_reg_con(Expression::new(vec![new_var("x", 1.0)],
+                                        Relationship::EQ,
+                                        vec![new_const("RHS", 2.0)])),
+            new_non_neg_con(new_var("x", 1.0)),
+        ]);
+        assert_eq!("Minimize\n P: 2.5 x - 3 y\n\
+                    Subject To\n con1: 3 x + y <= 90\n c2: x = 2\n\
+                    Bounds\n x >= 0\n\
+                    End\n",
+                   write_lp(&function, &constraints).unwrap());
+
+        let constraints = SystemOfConstraints::new(vec![new_stay_con(new_var("x", 1.0))]);
+        assert!(write_lp(&function, &constraints).is_err());
+    }
+
+    #[test]
+    fn can_parse_mps_ranges() {
+        let text = "ROWS\n N obj\n E r1\n E r2\n G r3\nCOLUMNS\n x obj 1 r1 1\n x r2 1 r3 1\n\
+                    RHS\n rhs r1 4 r2 4\n rhs r3 4\nRANGES\n rng r1 2 r2 -2\n rng r3 -3\n";
+        let (_, constraints) = parse_mps::<f64>(text, MpsFormat::Free).unwrap();
+        let system = constraints.system();
+        assert_eq!(6, system.len());
+        let expected = [(Relationship::GEQ, "r1", 4.0),
+                        (Relationship::LEQ, "r1_range", 6.0),
+                        (Relationship::LEQ, "r2", 4.0),
+                        (Relationship::GEQ, "r2_range", 2.0),
+                        (Relationship::GEQ, "r3", 4.0),
+                        (Relationship::LEQ, "r3_range", 7.0)];
+        for (constraint, &(ref rel, name, value)) in system.iter().zip(expected.iter()) {
+            assert_regular(constraint,
+                           Expression::new(vec![new_var("x", 1.0)],
+                                           rel.clone(),
+                                           vec![new_const(name, value)]));
+        }
+    }
+}
@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+use Num;
+use math::variables::{AbstVar, new_var, new_const, is_reserved_name};
+use math::relationships::Relationship;
+use math::expressions::Expression;
+use objective::problems::ProblemType;
+use objective::functions::Function;
+use objective::constraints::{Constraint, SystemOfConstraints, new_reg_con};
+use errors::{ParseError, SolveError};
+use format::rows::{get_model, get_bounds_of, new_bounds, get_reserved_name_reason};
+
+// Where each field of a fixed format line starts and ends, counting columns from zero.
+const FIXED_FIELDS: [(usize, usize); 6] =
+    [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+
+/// How the fields of an MPS file are laid out: in set columns, which lets names hold spaces, or
+/// separated by whitespace, which lets them be longer than eight characters.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MpsFormat {
+    Fixed,
+    Free,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+enum Section {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+}
+
+struct Field {
+    text: String,
+    column: usize,
+}
+
+// A row of the ROWS section and what later sections give it.
+struct Row<N> {
+    name: String,
+    rel: Relationship,
+    terms: Vec<AbstVar<N>>,
+    rhs: N,
+    range: Option<N>,
+    line: usize,
+}
+
+enum RowRef {
+    Objective,
+    // Rows of type N besides the first, which take no part in the model.
+    Free,
+    Constraint(usize),
+}
+
+/// Reads a model in the MPS format. Only the first right hand side, range and bound vector is
+/// used when a file has several. Bounds become `Constraint::Bounded`, but every variable needs a
+/// finite lower bound, so free variables are turned away along with integer ones.
+pub fn parse_mps<N: Num>(text: &str,
+                         format: MpsFormat)
+                         -> Result<(Function<N>, SystemOfConstraints<N>), ParseError> {
+    let mut section = None;
+    let mut p_type = ProblemType::MIN;
+    let mut objective: Option<(String, Vec<AbstVar<N>>)> = None;
+    let mut rows: Vec<Row<N>> = Vec::new();
+    let mut row_refs: HashMap<String, RowRef> = HashMap::new();
+    let mut columns = HashSet::new();
+    let mut bounds = Vec::new();
+    // The columns given a lower bound so far, which a negative upper bound needs.
+    let mut lower_bounded = HashSet::new();
+    let mut vectors: HashMap<Section, String> = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            let mut words = line.split_whitespace();
+            let header = words.next().unwrap_or("").to_uppercase();
+            section = Some(match header.as_str() {
+                "NAME" => Section::Name,
+                "OBJSENSE" => Section::ObjSense,
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "RANGES" => Section::Ranges,
+                "BOUNDS" => Section::Bounds,
+                "ENDATA" => break,
+                _ => {
+                    let reason = format!("unknown section \"{}\".", header);
+                    return Err(ParseError::new(number, 1, reason));
+                }
+            });
+            // The sense may follow on the same line.
+            if let (Some(Section::ObjSense), Some(sense)) = (section, words.next()) {
+                let column = line.find(sense).unwrap_or(0) + 1;
+                p_type = parse_sense(sense, number, column)?;
+            }
+            continue;
+        }
+        let fields = get_fields(line, format);
+        if let Some(field) = fields.iter().find(|field| is_reserved_name(&field.text)) {
+            let reason = get_reserved_name_reason(&field.text);
+            return Err(ParseError::new(number, field.column, reason));
+        }
+        let first = match fields.first() {
+            Some(field) => field,
+            None => {
+                let reason = "expected fields in the columns fixed MPS files keep them in.";
+                return Err(ParseError::new(number, 1, reason.to_string()));
+            }
+        };
+        match section {
+            None => {
+                let reason = "expected a section such as \"ROWS\" first.".to_string();
+                return Err(ParseError::new(number, first.column, reason));
+            }
+            Some(Section::Name) => {}
+            Some(Section::ObjSense) => p_type = parse_sense(&first.text, number, first.column)?,
+            Some(Section::Rows) => {
+                let name = match fields.get(1) {
+                    Some(field) => field.text.clone(),
+                    None => return Err(error_after(&fields, number, "expected a row name.")),
+                };
+                let rel = match first.text.to_uppercase().as_str() {
+                    "N" => {
+                        if objective.is_none() {
+                            objective = Some((name.clone(), Vec::new()));
+                            row_refs.insert(name, RowRef::Objective);
+                        } else {
+                            row_refs.insert(name, RowRef::Free);
+                        }
+                        continue;
+                    }
+                    "L" => Relationship::LEQ,
+                    "G" => Relationship::GEQ,
+                    "E" => Relationship::EQ,
+                    _ => {
+                        let reason = format!("unknown row type \"{}\".", first.text);
+                        return Err(ParseError::new(number, first.column, reason));
+                    }
+                };
+                row_refs.insert(name.clone(), RowRef::Constraint(rows.len()));
+                rows.push(Row {
+                    name,
+                    rel,
+                    terms: Vec::new(),
+                    rhs: N::zero(),
+                    range: None,
+                    line: number,
+                });
+            }
+            Some(Section::Columns) => {
+                if fields.iter().any(|field| field.text == "'MARKER'") {
+                    if fields.iter().any(|field| field.text == "'INTORG'") {
+                        let reason = "integer variables are not supported.";
+                        return Err(ParseError::new(number, first.column, reason.to_string()));
+                    }
+                    continue;
+                }
+                columns.insert(first.text.clone());
+                for (row, value) in get_pairs::<N>(&fields[1..], number)? {
+                    let var = new_var(&first.text, value);
+                    match row_refs.get(&row.text) {
+                        Some(&RowRef::Objective) => {
+                            if let Some((_, ref mut terms)) = objective {
+                                terms.push(var);
+                            }
+                        }
+                        Some(&RowRef::Free) => {}
+                        Some(&RowRef::Constraint(index)) => rows[index].terms.push(var),
+                        None => return Err(unknown_row_error(row, number)),
+                    }
+                }
+            }
+            Some(Section::Rhs) |
+            Some(Section::Ranges) => {
+                let (is_rhs, key) = match section {
+                    Some(Section::Rhs) => (true, Section::Rhs),
+                    _ => (false, Section::Ranges),
+                };
+                // The vector's name is left out as often as not.
+                let start = fields.len() % 2;
+                if start == 1 && !is_first_vector(&mut vectors, key, &first.text) {
+                    continue;
+                }
+                for (row, value) in get_pairs::<N>(&fields[start..], number)? {
+                    match row_refs.get(&row.text) {
+                        Some(&RowRef::Objective) if value != N::zero() => {
+                            let reason = "constants in the objective are not supported.";
+                            return Err(ParseError::new(number, row.column, reason.to_string()));
+                        }
+                        Some(&RowRef::Objective) |
+                        Some(&RowRef::Free) => {}
+                        Some(&RowRef::Constraint(index)) if is_rhs => rows[index].rhs = value,
+                        Some(&RowRef::Constraint(index)) => rows[index].range = Some(value),
+                        None => return Err(unknown_row_error(row, number)),
+                    }
+                }
+            }
+            Some(Section::Bounds) => {
+                let kind = first.text.to_uppercase();
+                let has_value = match kind.as_str() {
+                    "UP" | "LO" | "FX" => true,
+                    "FR" | "MI" | "PL" => false,
+                    "BV" | "LI" | "UI" | "SC" => {
+                        let reason = "integer variables are not supported.";
+                        return Err(ParseError::new(number, first.column, reason.to_string()));
+                    }
+                    _ => {
+                        let reason = format!("unknown bound type \"{}\".", first.text);
+                        return Err(ParseError::new(number, first.column, reason));
+                    }
+                };
+                let with_vector = if has_value {
+                    fields.len() > 3
+                } else {
+                    fields.len() > 2
+                };
+                if with_vector && !is_first_vector(&mut vectors, Section::Bounds, &fields[1].text) {
+                    continue;
+                }
+                let column = match fields.get(if with_vector { 2 } else { 1 }) {
+                    Some(field) => field,
+                    None => return Err(error_after(&fields, number, "expected a column name.")),
+                };
+                if !columns.contains(&column.text) {
+                    let reason = format!("unknown column \"{}\".", column.text);
+                    return Err(ParseError::new(number, column.column, reason));
+                }
+                let value = if has_value {
+                    match fields.get(if with_vector { 3 } else { 2 }) {
+                        Some(field) => parse_number::<N>(field, number)?,
+                        None => return Err(error_after(&fields, number, "expected a number.")),
+                    }
+                } else {
+                    N::zero()
+                };
+                // Readers take a negative upper bound on its own to make the column free below.
+                if kind == "UP" && value < N::zero() && !lower_bounded.contains(&column.text) {
+                    return Err(unbounded_below_error(number, first.column));
+                }
+                if kind == "LO" || kind == "FX" {
+                    lower_bounded.insert(column.text.clone());
+                }
+                let bound = get_bounds_of(&mut bounds, &column.text);
+                match kind.as_str() {
+                    "UP" => bound.2 = Some(value),
+                    "LO" => bound.1 = value,
+                    "FX" => {
+                        bound.1 = value.clone();
+                        bound.2 = Some(value);
+                    }
+                    "PL" => {}
+                    _ => return Err(unbounded_below_error(number, first.column)),
+                }
+            }
+        }
+    }
+    let (name, terms) = match objective {
+        Some(objective) => objective,
+        None => {
+            let reason = "expected a row of type N for the objective.".to_string();
+            return Err(ParseError::new(1, 1, reason));
+        }
+    };
+    let mut exp = Expression::new(vec![new_var(&name, N::one())], Relationship::EQ, Vec::new());
+    for term in terms {
+        exp.add_rhs(term);
+    }
+    let mut constraints = Vec::new();
+    for row in rows {
+        constraints.extend(get_constraints(row)?);
+    }
+    constraints.extend(bounds.into_iter()
+        .map(|(name, lower, upper)| new_bounds(&name, lower, upper)));
+    Ok((Function::new(exp, p_type), SystemOfConstraints::new(constraints)))
+}
+
+/// Writes a model in the MPS format, the same before or after it has been solved. The sense of
+/// a maximisation goes in an OBJSENSE section, which most readers understand.
+pub fn write_mps<N: Num>(function: &Function<N>,
+                         constraints: &SystemOfConstraints<N>,
+                         format: MpsFormat)
+                         -> Result<String, SolveError<N>> {
+    let model = get_model(function, constraints, |name| is_name(name, format))?;
+    let objective = function.name().to_string();
+    let mut text = String::new();
+    text.push_str("NAME\n");
+    if *function.p_type() == ProblemType::MAX {
+        text.push_str("OBJSENSE\n    MAX\n");
+    }
+    text.push_str("ROWS\n");
+    write_fields(&mut text, format, &["N", &objective])?;
+    for row in &model.rows {
+        let kind = match row.rel {
+            Relationship::LEQ => "L",
+            Relationship::GEQ => "G",
+            Relationship::EQ => "E",
+        };
+        write_fields(&mut text, format, &[kind, &row.label])?;
+    }
+    // Each column's entries have to come together.
+    let mut columns: Vec<(String, Vec<(&str, &N)>)> = Vec::new();
+    let entries = model.objective
+        .iter()
+        .map(|term| (objective.as_str(), term))
+        .chain(model.rows
+            .iter()
+            .flat_map(|row| row.terms.iter().map(move |term| (row.label.as_str(), term))));
+    for (row, (column, value)) in entries {
+        match columns.iter_mut().find(|entry| entry.0 == *column) {
+            Some(entry) => entry.1.push((row, value)),
+            None => columns.push((column.clone(), vec![(row, value)])),
+        }
+    }
+    text.push_str("COLUMNS\n");
+    for (column, entries) in columns {
+        for (row, value) in entries {
+            write_fields(&mut text, format, &["", &column, row, &value.to_decimal()])?;
+        }
+    }
+    text.push_str("RHS\n");
+    for row in model.rows.iter().filter(|row| row.rhs != N::zero()) {
+        write_fields(&mut text, format, &["", "RHS", &row.label, &row.rhs.to_decimal()])?;
+    }
+    // Every variable starts out non-negative, so only other bounds need writing.
+    let bounds = model.bounds
+        .iter()
+        .filter(|&(_, lower, upper)| *lower != N::zero() || upper.is_some())
+        .collect::<Vec<_>>();
+    if !bounds.is_empty() {
+        text.push_str("BOUNDS\n");
+    }
+    for (column, lower, upper) in bounds {
+        match *upper {
+            Some(ref upper) if upper == lower => {
+                write_fields(&mut text, format, &["FX", "BND", column, &upper.to_decimal()])?;
+                continue;
+            }
+            Some(ref upper) => {
+                write_fields(&mut text, format, &["UP", "BND", column, &upper.to_decimal()])?;
+            }
+            None => {}
+        }
+        if *lower != N::zero() {
+            write_fields(&mut text, format, &["LO", "BND", column, &lower.to_decimal()])?;
+        }
+    }
+    text.push_str("ENDATA\n");
+    Ok(text)
+}
+
+fn get_fields(line: &str, format: MpsFormat) -> Vec<Field> {
+    let mut fields = Vec::new();
+    match format {
+        MpsFormat::Fixed => {
+            let chars = line.chars().collect::<Vec<char>>();
+            for &(start, end) in FIXED_FIELDS.iter() {
+                if start >= chars.len() {
+                    break;
+                }
+                let field = chars[start..end.min(chars.len())].iter().collect::<String>();
+                let trimmed = field.trim();
+                if !trimmed.is_empty() {
+                    let leading = field.chars().take_while(|c| c.is_whitespace()).count();
+                    fields.push(Field {
+                        text: trimmed.to_string(),
+                        column: start + leading + 1,
+                    });
+                }
+            }
+        }
+        MpsFormat::Free => {
+            let mut start = None;
+            for (i, c) in line.chars().chain(Some(' ')).enumerate() {
+                match (c.is_whitespace(), start) {
+                    (false, None) => start = Some(i),
+                    (true, Some(begin)) => {
+                        fields.push(Field {
+                            text: line.chars().skip(begin).take(i - begin).collect(),
+                            column: begin + 1,
+                        });
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    fields
+}
+
+fn is_name(name: &str, format: MpsFormat) -> bool {
+    match format {
+        MpsFormat::Fixed => {
+            name.chars().count() <= 8 && !name.starts_with(char::is_whitespace) &&
+            !name.ends_with(char::is_whitespace)
+        }
+        MpsFormat::Free => !name.chars().any(char::is_whitespace),
+    }
+}
+
+fn write_fields<N>(text: &mut String,
+                   format: MpsFormat,
+                   fields: &[&str])
+                   -> Result<(), SolveError<N>> {
+    let mut line = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        let is_number = i == 3;
+        if !is_number && i > 0 && (field.is_empty() || !is_name(field, format)) {
+            let reason = format!("\"{}\" is not a name MPS files can hold.", field);
+            return Err(SolveError::MalformedModel(reason));
+        }
+        match format {
+            MpsFormat::Fixed => {
+                let (start, end) = FIXED_FIELDS[i];
+                if field.chars().count() > end - start {
+                    let reason = format!("\"{}\" does not fit in a fixed MPS field.", field);
+                    return Err(SolveError::MalformedModel(reason));
+                }
+                while line.chars().count() < start {
+                    line.push(' ');
+                }
+            }
+            MpsFormat::Free if field.is_empty() => continue,
+            MpsFormat::Free => line.push_str(if line.is_empty() && i > 0 { "    " } else { " " }),
+        }
+        line.push_str(field);
+    }
+    text.push_str(line.trim_end());
+    text.push('\n');
+    Ok(())
+}
+
+fn parse_sense(text: &str, line: usize, column: usize) -> Result<ProblemType, ParseError> {
+    match text.to_uppercase().as_str() {
+        "MAX" | "MAXIMIZE" | "MAXIMISE" => Ok(ProblemType::MAX),
+        "MIN" | "MINIMIZE" | "MINIMISE" => Ok(ProblemType::MIN),
+        _ => Err(ParseError::new(line, column, "expected \"MAX\" or \"MIN\".".to_string())),
+    }
+}
+
+fn parse_number<N: Num>(field: &Field, line: usize) -> Result<N, ParseError> {
+    N::from_decimal(&field.text).ok_or_else(|| {
+        ParseError::new(line, field.column, format!("\"{}\" is not a number.", field.text))
+    })
+}
+
+// Row names and the numbers that go with them, in one or two pairs.
+fn get_pairs<N: Num>(fields: &[Field], line: usize) -> Result<Vec<(&Field, N)>, ParseError> {
+    if fields.is_empty() || fields.len() % 2 == 1 || fields.len() > 4 {
+        return Err(error_after(fields, line, "expected a row name and a number."));
+    }
+    fields.chunks(2).map(|pair| Ok((&pair[0], parse_number(&pair[1], line)?))).collect()
+}
+
+// Whether a line belongs to the first vector of its section, which is the only one read.
+fn is_first_vector(vectors: &mut HashMap<Section, String>, section: Section, name: &str) -> bool {
+    vectors.entry(section).or_insert_with(|| name.to_string()) == name
+}
+
+fn get_constraints<N: Num>(row: Row<N>) -> Result<Vec<Constraint<N>>, ParseError> {
+    if row.terms.is_empty() {
+        let holds = match row.rel {
+            Relationship::LEQ => row.rhs >= N::zero(),
+            Relationship::GEQ => row.rhs <= N::zero(),
+            Relationship::EQ => row.rhs == N::zero(),
+        };
+        if holds {
+            return Ok(Vec::new());
+        }
+        let reason = format!("row \"{}\" has no columns, so it can never hold.", row.name);
+        return Err(ParseError::new(row.line, 1, reason));
+    }
+    // A range turns a row into two, with the range between their constants.
+    let (rel, other) = match (row.rel.clone(), row.range.clone()) {
+        (rel, None) => (rel, None),
+        (Relationship::EQ, Some(ref range)) if *range == N::zero() => (Relationship::EQ, None),
+        (Relationship::EQ, Some(range)) => {
+            if range > N::zero() {
+                (Relationship::GEQ, Some((Relationship::LEQ, row.rhs.clone() + range)))
+            } else {
+                (Relationship::LEQ, Some((Relationship::GEQ, row.rhs.clone() + range)))
+            }
+        }
+        (Relationship::LEQ, Some(range)) => {
+            (Relationship::LEQ, Some((Relationship::GEQ, row.rhs.clone() - abs(range))))
+        }
+        (Relationship::GEQ, Some(range)) => {
+            (Relationship::GEQ, Some((Relationship::LEQ, row.rhs.clone() + abs(range))))
+        }
+    };
+    let mut exp = Expression::new(Vec::new(), rel, vec![new_const(&row.name, row.rhs.clone())]);
+    for term in row.terms {
+        exp.add_lhs(term);
+    }
+    let name = row.name;
+    let range = other.map(|(rel, rhs)| {
+        let label = format!("{}_range", name);
+        new_reg_con(Expression::new(exp.lhs().clone(), rel, vec![new_const(&label, rhs)]))
+    });
+    Ok(Some(new_reg_con(exp)).into_iter().chain(range).collect())
+}
+
+fn abs<N: Num>(number: N) -> N {
+    if number < N::zero() { -number } else { number }
+}
+
+fn error_after(fields: &[Field], line: usize, message: &str) -> ParseError {
+    let column = fields.last().map_or(1, |field| field.column + field.text.chars().count());
+    ParseError::new(line, column, message.to_string())
+}
+
+fn unknown_row_error(row: &Field, line: usize) -> ParseError {
+    ParseError::new(line, row.column, format!("unknown row \"{}\".", row.text))
+}
+
+fn unbounded_below_error(line: usize, column: usize) -> ParseError {
+    ParseError::new(line,
+                    column,
+                    "variables need a finite lower bound, so free ones are not supported."
+                        .to_string())
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use Num;
+use math::variables::{AbstVar, new_var};
+use math::relationships::Relationship;
+use math::expressions::Expression;
+use objective::functions::Function;
+use objective::constraints::{Constraint, SystemOfConstraints, new_non_neg_con,
+                             new_bounded_con};
+use errors::SolveError;
+
+/// A constraint the way model files hold it: the variables on the left and a constant alone on
+/// the right.
+pub struct Row<N> {
+    pub label: String,
+    pub terms: Vec<(String, N)>,
+    pub rel: Relationship,
+    pub rhs: N,
+}
+
+/// A model taken apart into what every file format writes out.
+pub struct Model<N> {
+    pub objective: Vec<(String, N)>,
+    pub rows: Vec<Row<N>>,
+    // The lower and any upper bound of each variable with bounds, non-negativity included.
+    pub bounds: Vec<(String, N, Option<N>)>,
+}
+
+/// Takes a model apart, the same before or after it has been solved. Rows are labelled by the
+/// names of their constants where `is_label` allows it and those are not taken already.
+pub fn get_model<N, F>(function: &Function<N>,
+                       constraints: &SystemOfConstraints<N>,
+                       is_label: F)
+                       -> Result<Model<N>, SolveError<N>>
+    where N: Num,
+          F: Fn(&str) -> bool
+{
+    let mut labels = HashSet::new();
+    let mut rows = Vec::new();
+    let mut bounds = Vec::new();
+    for (i, constraint) in constraints.system().iter().enumerate() {
+        match *constraint {
+            Constraint::Regular(ref exp) => {
+                let (terms, rel, rhs, label) = get_constraint_terms(exp);
+                let label = match label {
+                    Some(label) if label != "RHS" && is_label(&label) &&
+                                   !labels.contains(&label) => label,
+                    _ => format!("c{}", i + 1),
+                };
+                labels.insert(label.clone());
+                rows.push(Row {
+                    label,
+                    terms,
+                    rel,
+                    rhs,
+                });
+            }
+            Constraint::NonNegative(ref var) => {
+                bounds.push((var.name().to_string(), N::zero(), None))
+            }
+            Constraint::Bounded(ref var, ref lower, ref upper) => {
+                bounds.push((var.name().to_string(), lower.clone(), upper.clone()))
+            }
+            Constraint::Weighted(..) |
+            Constraint::Stay(_) => {
+                let reason = format!("constraint {} is not a hard linear constraint, which model \
+                                      files can not hold.",
+                                     i + 1);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    Ok(Model {
+        objective: get_objective_terms(function)?,
+        rows,
+        bounds,
+    })
+}
+
+// The function as coefficients of the variables it is equal to, however its expression has
+// been rearranged.
+fn get_objective_terms<N: Num>(function: &Function<N>) -> Result<Vec<(String, N)>, SolveError<N>> {
+    let name = function.name();
+    let exp = function.exp();
+    let mut scale = N::zero();
+    let mut terms: Vec<(String, N)> = Vec::new();
+    let sides = exp.lhs().iter().map(|var| (var, -N::one())).chain(exp.rhs().iter().map(|var| {
+        (var, N::one())
+    }));
+    for (var, sign) in sides {
+        match *var {
+            AbstVar::Constant { .. } => continue,
+            _ if *var.name() == name => scale -= sign * var.get_data(),
+            _ => add_term(&mut terms, &var.name().to_string(), sign * var.get_data()),
+        }
+    }
+    if scale == N::zero() {
+        let reason = format!("function \"{}\" does not depend on its own variable.", name);
+        return Err(SolveError::MalformedModel(reason));
+    }
+    Ok(terms.into_iter().map(|(name, coefficient)| (name, coefficient / scale.clone())).collect())
+}
+
+// The variables of a constraint on the left and its constants on the right, along with the
+// name of the constant. Solving turns inequalities into equations with a slack or surplus
+// variable, which gives away the relationship the constraint started with.
+fn get_constraint_terms<N: Num>(exp: &Expression<N>)
+                                -> (Vec<(String, N)>, Relationship, N, Option<String>) {
+    let mut terms: Vec<(String, N)> = Vec::new();
+    let mut rel = exp.rel().clone();
+    let mut rhs = N::zero();
+    let mut label = None;
+    let sides = exp.lhs().iter().map(|var| (var, N::one())).chain(exp.rhs().iter().map(|var| {
+        (var, -N::one())
+    }));
+    for (var, sign) in sides {
+        match *var {
+            AbstVar::Constant { ref name, ref value } => {
+                rhs -= sign * value.clone();
+                if label.is_none() {
+                    label = Some(name.to_string());
+                }
+            }
+            AbstVar::SlackVar { .. } |
+            AbstVar::SurplusVar { .. } => {
+                rel = if sign * var.get_data() > N::zero() {
+                    Relationship::LEQ
+                } else {
+                    Relationship::GEQ
+                };
+            }
+            AbstVar::ArtiVar { .. } => continue,
+            _ => add_term(&mut terms, &var.name().to_string(), sign * var.get_data()),
+        }
+    }
+    (terms, rel, rhs, label)
+}
+
+fn add_term<N: Num>(terms: &mut Vec<(String, N)>, name: &str, coefficient: N) {
+    match terms.iter_mut().find(|term| term.0 == name) {
+        Some(term) => term.1 += coefficient,
+        None => terms.push((name.to_string(), coefficient)),
+    }
+}
+
+/// The bounds a model file has given a variable so far, which start out as zero and none.
+pub fn get_bounds_of<'a, N: Num>(bounds: &'a mut Vec<(String, N, Option<N>)>,
+                                 name: &str)
+                                 -> &'a mut (String, N, Option<N>) {
+    let index = match bounds.iter().position(|bound| bound.0 == name) {
+        Some(index) => index,
+        None => {
+            bounds.push((name.to_string(), N::zero(), None));
+            bounds.len() - 1
+        }
+    };
+    &mut bounds[index]
+}
+
+/// The bounds of a single variable as a constraint. A lower bound of zero and no upper one are
+/// what every variable has already.
+pub fn new_bounds<N: Num>(name: &str, lower: N, upper: Option<N>) -> Constraint<N> {
+    if lower == N::zero() && upper.is_none() {
+        new_non_neg_con(new_var(name, N::one()))
+    } else {
+        new_bounded_con(new_var(name, N::one()), lower, upper)
+    }
+}
+
+/// Why a model file can not name a variable like a handle.
+pub fn get_reserved_name_reason(name: &str) -> String {
+    format!("\"{}\" reads like a variable handle.", name)
+}
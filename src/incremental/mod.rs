@@ -0,0 +1,63 @@
+pub mod solvers;
+
+#[cfg(test)]
+mod tests {
+    use math::variables::{new_var, new_const};
+    use math::relationships::Relationship;
+    use math::expressions::Expression;
+    use objective::problems::ProblemType;
+    use objective::functions::Function;
+    use objective::constraints::{new_reg_con, new_non_neg_con};
+    use incremental::solvers::Solver;
+    use errors::SolveError;
+
+    #[test]
+    fn can_create_solvers() {
+        let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_var("x", 2.0), new_var("y", 3.0)]);
+        let solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+        let solution = solver.solution().unwrap();
+        assert_eq!(0.0, solution.objective());
+    }
+
+    #[test]
+    fn can_add_constraints() {
+        let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_var("x", 2.0), new_var("y", 3.0)]);
+        let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                                   Relationship::GEQ,
+                                   vec![new_const("con1", 10.0)]);
+        let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+        let handle1 = solver.add_constraint(new_reg_con(exp2)).unwrap();
+        let handle2 = solver.add_constraint(new_non_neg_con(new_var("x", 1.0))).unwrap();
+        assert!(solver.has_constraint(&handle1));
+        assert!(solver.has_constraint(&handle2));
+        assert!(handle1 != handle2);
+        let solution = solver.solution().unwrap();
+        assert_eq!(Some(10.0), solution.value("x"));
+        assert_eq!(20.0, solution.objective());
+    }
+
+    #[test]
+    fn can_remove_constraints() {
+        let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_var("x", 2.0), new_var("y", 3.0)]);
+        let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                                   Relationship::GEQ,
+                                   vec![new_const("con1", 10.0)]);
+        let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+        let handle = solver.add_constraint(new_reg_con(exp2)).unwrap();
+        solver.remove_constraint(&handle).unwrap();
+        assert!(!solver.has_constraint(&handle));
+        let solution = solver.solution().unwrap();
+        assert_eq!(0.0, solution.objective());
+        assert_eq!(None, solution.internal_value("su1"));
+        match solver.remove_constraint(&handle) {
+            Err(SolveError::MalformedModel(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+use math::variables::{AbstVar, Name, Variable, Variables, VarKey, new_const, new_slack_var,
+                      new_surplus_var, new_arti_var};
+use math::relationships::Relationship;
+use math::expressions::Expression;
+use objective::functions::Function;
+use objective::constraints::{Constraint, SystemOfConstraints, new_weighted_con};
+use objective::strengths::Strength;
+use objective::solvers::{rearrange_fun_eq_zero, get_error_vars};
+use tableau::initials::get_initial_table_from;
+use tableau::pivots::pivot_around;
+use tableau::tables::{Table, ColumnKind};
+use errors::SolveError;
+use solutions::{Solution, Status};
+use options::Options;
+use {Num, Budget, check_function, check_constraint, get_constraint_vars, run_simplex,
+     run_dual_simplex, get_solution, get_basic_solution_of, is_w_zero, limit_error};
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct ConstraintHandle {
+    id: usize,
+}
+
+// The columns a constraint brought into the table, its marker variable first, the sign its row
+// took on to keep the RHS non-negative and the sign that turns the function row entry under the
+// marker into the dual value of the constraint, along with the RHS it currently has.
+struct Tag<N> {
+    columns: Vec<Name>,
+    sign: N,
+    dual_sign: N,
+    rhs: N,
+}
+
+// Edit and stay constraints pin a variable to a value that moves between solves.
+struct Anchor<N> {
+    name: Name,
+    value: N,
+}
+
+pub struct Solver<N> {
+    variables: Variables,
+    function: Function<N>,
+    table: Table<N>,
+    constraints: HashMap<ConstraintHandle, Tag<N>>,
+    anchors: HashMap<ConstraintHandle, Anchor<N>>,
+    edits: HashMap<Name, ConstraintHandle>,
+    stays: Vec<ConstraintHandle>,
+    next_id: usize,
+    solution: Result<Solution<N>, SolveError<N>>,
+    options: Options<N>,
+}
+
+impl<N: Num> Solver<N> {
+    pub fn new(function: Function<N>) -> Result<Solver<N>, SolveError<N>> {
+        Solver::with_options(function, &Options::default())
+    }
+
+    pub fn with_options(function: Function<N>,
+                        options: &Options<N>)
+                        -> Result<Solver<N>, SolveError<N>> {
+        Solver::with_variables(function, Variables::new(), options)
+    }
+
+    /// A solver that goes on handing out variables from the registry the function took its
+    /// handles from.
+    pub fn with_variables(mut function: Function<N>,
+                          variables: Variables,
+                          options: &Options<N>)
+                          -> Result<Solver<N>, SolveError<N>> {
+        check_function(&function)?;
+        let function_vars = function.exp().lhs().iter().chain(function.exp().rhs());
+        check_owned(&variables, function_vars, "function")?;
+        rearrange_fun_eq_zero(&mut function);
+        let mut table = get_initial_table_from(&function, &SystemOfConstraints::new(Vec::new()));
+        table.set_tolerances(options.tolerances().clone());
+        let mut budget = Budget::new(options);
+        let solution = run_simplex(&mut table, options, &mut budget).and_then(|status| {
+            get_solution(&function, &table, budget.pivots, &[], status)
+        });
+        Ok(Solver {
+            variables,
+            function,
+            table,
+            constraints: HashMap::new(),
+            anchors: HashMap::new(),
+            edits: HashMap::new(),
+            stays: Vec::new(),
+            next_id: 1,
+            solution,
+            options: options.clone(),
+        })
+    }
+
+    pub fn add_var(&mut self, name: &str) -> Variable {
+        self.variables.add(name)
+    }
+
+    pub fn add_unnamed_var(&mut self) -> Variable {
+        self.variables.add_unnamed()
+    }
+
+    /// The variables the solver has handed out, which know their names.
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    pub fn solution(&self) -> Result<Solution<N>, SolveError<N>> {
+        self.solution.clone()
+    }
+
+    pub fn dual(&self, handle: &ConstraintHandle) -> Option<N> {
+        match self.solution {
+            Ok(ref solution) => solution.dual(handle.id),
+            Err(_) => None,
+        }
+    }
+
+    pub fn has_constraint(&self, handle: &ConstraintHandle) -> bool {
+        self.constraints.contains_key(handle)
+    }
+
+    pub fn add_constraint(&mut self,
+                          constraint: Constraint<N>)
+                          -> Result<ConstraintHandle, SolveError<N>> {
+        let handle = ConstraintHandle { id: self.next_id };
+        check_constraint(&constraint, handle.id)?;
+        let whose = format!("constraint {}", handle.id);
+        check_owned(&self.variables, get_constraint_vars(&constraint), &whose)?;
+        let (exp, weight) = match constraint {
+            Constraint::Regular(exp) => (exp, None),
+            Constraint::Weighted(exp, strength) => (exp, strength.weight()),
+            Constraint::NonNegative(_) => {
+                // Every variable in the table is non-negative already.
+                self.next_id += 1;
+                self.constraints.insert(handle,
+                                        Tag {
+                                            columns: Vec::new(),
+                                            sign: N::one(),
+                                            dual_sign: N::one(),
+                                            rhs: N::zero(),
+                                        });
+                return Ok(handle);
+            }
+            Constraint::Bounded(..) => {
+                let reason = format!("constraint {} bounds a variable, which the incremental \
+                                      solver does not support; add the bounds as constraints.",
+                                     handle.id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+            Constraint::Stay(var) => {
+                // Hold the variable where it is now; the anchor follows it after every solve.
+                let handle = self.add_anchor(var.name(), Strength::Weak)?;
+                self.stays.push(handle);
+                return Ok(handle);
+            }
+        };
+        // Leave the table as it was if the constraint can not be satisfied.
+        let snapshot = self.table.clone();
+        let mut budget = Budget::new(&self.options);
+        match self.insert_row(exp, weight, handle.id, &mut budget) {
+            Ok(tag) => {
+                self.next_id += 1;
+                self.constraints.insert(handle, tag);
+                self.resolve(&mut budget);
+                Ok(handle)
+            }
+            Err(error) => {
+                self.table = snapshot;
+                Err(error)
+            }
+        }
+    }
+
+    pub fn remove_constraint(&mut self, handle: &ConstraintHandle) -> Result<(), SolveError<N>> {
+        let columns = match self.constraints.get(handle) {
+            Some(tag) => tag.columns.clone(),
+            None => {
+                let reason = format!("constraint {} is not in the solver.", handle.id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        };
+        if let Some(marker) = columns.first() {
+            let marker_row = self.pivot_marker_into_basis(marker)?;
+            self.table.remove_row(marker_row);
+            for column in &columns {
+                self.table.remove_column(column);
+            }
+        }
+        self.constraints.remove(handle);
+        self.anchors.remove(handle);
+        self.edits.retain(|_, edit| *edit != *handle);
+        self.stays.retain(|stay| *stay != *handle);
+        self.resolve(&mut Budget::new(&self.options));
+        Ok(())
+    }
+
+    pub fn add_edit_var<K>(&mut self, var: &K, strength: Strength<N>) -> Result<(), SolveError<N>>
+        where K: VarKey + ?Sized
+    {
+        let name = var.var_key();
+        if name.handle().is_some_and(|handle| !self.variables.owns(handle)) {
+            let reason = format!("edit variable \"{}\" was not handed out by the solver.", name);
+            return Err(SolveError::MalformedModel(reason));
+        }
+        if self.edits.contains_key(&name) {
+            let reason = format!("\"{}\" is already an edit variable.", name);
+            return Err(SolveError::MalformedModel(reason));
+        }
+        if strength == Strength::Required {
+            let reason = format!("edit variable \"{}\" can not be required.", name);
+            return Err(SolveError::MalformedModel(reason));
+        }
+        let handle = self.add_anchor(&name, strength)?;
+        self.edits.insert(name, handle);
+        Ok(())
+    }
+
+    pub fn remove_edit_var<K: VarKey + ?Sized>(&mut self, var: &K) -> Result<(), SolveError<N>> {
+        let handle = self.get_edit_handle(var)?;
+        self.remove_constraint(&handle)
+    }
+
+    pub fn has_edit_var<K: VarKey + ?Sized>(&self, var: &K) -> bool {
+        self.get_edit_handle(var).is_ok()
+    }
+
+    pub fn suggest_value<K>(&mut self, var: &K, value: N) -> Result<(), SolveError<N>>
+        where K: VarKey + ?Sized
+    {
+        let handle = self.get_edit_handle(var)?;
+        let old_value = match self.anchors.get(&handle) {
+            Some(anchor) => anchor.value.clone(),
+            None => {
+                let reason = format!("constraint {} is not an anchor.", handle.id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        };
+        // Moving the anchor keeps the function row optimal but may leave basic variables
+        // negative, which the dual simplex then pivots away.
+        let snapshot = self.table.clone();
+        self.move_anchor(&handle, value)?;
+        let mut budget = Budget::new(&self.options);
+        let error = match run_dual_simplex(&mut self.table, &mut budget) {
+            Ok(Status::Optimal) => {
+                self.resolve(&mut budget);
+                return Ok(());
+            }
+            // A dual simplex stopped part way has basic variables that are still negative, so
+            // there is nothing to hand back.
+            Ok(status) => limit_error(status).unwrap_or(SolveError::IterationLimit),
+            Err(error) => error,
+        };
+        // Put the anchor back where it was along with the table.
+        self.move_anchor(&handle, old_value)?;
+        self.table = snapshot;
+        Err(error)
+    }
+
+    fn get_edit_handle<K>(&self, var: &K) -> Result<ConstraintHandle, SolveError<N>>
+        where K: VarKey + ?Sized
+    {
+        let name = var.var_key();
+        match self.edits.get(&name) {
+            Some(handle) => Ok(*handle),
+            None => {
+                let reason = format!("\"{}\" is not an edit variable.", name);
+                Err(SolveError::MalformedModel(reason))
+            }
+        }
+    }
+
+    fn add_anchor(&mut self,
+                  name: &Name,
+                  strength: Strength<N>)
+                  -> Result<ConstraintHandle, SolveError<N>> {
+        // Start from wherever the variable currently is so adding the anchor moves nothing.
+        let value = self.value_of(name);
+        let var = AbstVar::Variable {
+            name: name.clone(),
+            coefficient: N::one(),
+        };
+        let exp = Expression::new(vec![var],
+                                  Relationship::EQ,
+                                  vec![new_const("anchor", value.clone())]);
+        let handle = self.add_constraint(new_weighted_con(exp, strength))?;
+        self.anchors.insert(handle,
+                            Anchor {
+                                name: name.clone(),
+                                value,
+                            });
+        Ok(handle)
+    }
+
+    fn move_anchor(&mut self, handle: &ConstraintHandle, value: N) -> Result<(), SolveError<N>> {
+        let delta = match self.anchors.get_mut(handle) {
+            Some(anchor) => {
+                let delta = value.clone() - anchor.value.clone();
+                anchor.value = value;
+                delta
+            }
+            None => {
+                let reason = format!("constraint {} is not an anchor.", handle.id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        };
+        // The marker column holds what one more unit on the RHS of the anchor does to every
+        // row, so shift each RHS by delta of it.
+        let (marker_column, sign) = match self.constraints.get_mut(handle) {
+            Some(tag) => {
+                tag.rhs += delta.clone();
+                (tag.columns[0].clone(), tag.sign.clone())
+            }
+            None => {
+                let reason = format!("constraint {} is not in the solver.", handle.id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        };
+        let marker_column = self.column_of(&marker_column)?;
+        let rhs_column = self.table.get_column_names().len() - 1;
+        for i in 0..self.table.get_rows().len() {
+            let shift = delta.clone() * sign.clone() *
+                        self.table.get_rows()[i][marker_column].clone();
+            if shift != N::zero() {
+                self.table.sub_cell(i, rhs_column, -shift);
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, budget: &mut Budget) {
+        self.solution = self.solve(budget);
+        if self.stays.is_empty() || self.solution.is_err() {
+            return;
+        }
+        // Re-anchor the stays on the values just found. That only zeroes their errors so the
+        // table stays optimal and the variables stay put.
+        for handle in self.stays.clone() {
+            let value = match self.anchors.get(&handle) {
+                Some(anchor) => self.value_of(&anchor.name),
+                None => continue,
+            };
+            if let Err(error) = self.move_anchor(&handle, value) {
+                self.solution = Err(error);
+                return;
+            }
+        }
+        self.solution = self.solve(budget);
+    }
+
+    fn solve(&mut self, budget: &mut Budget) -> Result<Solution<N>, SolveError<N>> {
+        let status = run_simplex(&mut self.table, &self.options, budget)?;
+        let mut dual_markers = self.constraints
+            .iter()
+            .filter(|&(_, tag)| !tag.columns.is_empty())
+            .map(|(handle, tag)| {
+                (handle.id, tag.columns[0].clone(), tag.dual_sign.clone(), tag.rhs.clone())
+            })
+            .collect::<Vec<(usize, Name, N, N)>>();
+        dual_markers.sort_by_key(|marker| marker.0);
+        get_solution(&self.function, &self.table, budget.pivots, &dual_markers, status)
+    }
+
+    fn value_of(&self, name: &Name) -> N {
+        match self.solution {
+            Ok(ref solution) => solution.value(name).unwrap_or(N::zero()),
+            Err(_) => N::zero(),
+        }
+    }
+
+    fn insert_row(&mut self,
+                  mut exp: Expression<N>,
+                  weight: Option<N>,
+                  id: usize,
+                  budget: &mut Budget)
+                  -> Result<Tag<N>, SolveError<N>> {
+        // The marker identifies the row of the constraint when the time comes to remove it.
+        let rel = exp.rel().clone();
+        let marker = match rel {
+            Relationship::LEQ => new_slack_var(format!("{}{}", "sl", id)),
+            Relationship::GEQ => new_surplus_var(format!("{}{}", "su", id)),
+            Relationship::EQ => new_arti_var(format!("{}{}", "arti", id)),
+        };
+        let mut columns = vec![marker.name().clone()];
+        let mut error_columns = Vec::new();
+        if weight.is_some() {
+            for error_var in get_error_vars(&rel, id) {
+                error_columns.push(error_var.name().clone());
+                exp.add_lhs(error_var);
+            }
+        }
+        if rel != Relationship::EQ {
+            exp.add_lhs(marker.clone());
+        }
+        // Make room for the variables the table has not seen yet...
+        for var in exp.lhs() {
+            if !self.table.get_column_names().contains_key(var.name()) {
+                self.table.append_empty_column(var.name().clone());
+                let column = self.column_of(var.name())?;
+                self.table.set_column_kind(column, ColumnKind::of(var));
+            }
+        }
+        // ... and charge every unit of error to the function.
+        let fun_row = self.table.get_rows().len() - 1;
+        if let Some(weight) = weight {
+            for error_column in &error_columns {
+                let column = self.column_of(error_column)?;
+                self.table.set_cell(fun_row, column, weight.clone());
+            }
+        }
+        columns.extend(error_columns.iter().cloned());
+
+        let num_columns = self.table.get_column_names().len();
+        let mut row = vec![N::zero(); num_columns];
+        for var in exp.lhs() {
+            row[self.column_of(var.name())?] += var.get_data();
+        }
+        let rhs = exp.rhs()[0].get_data();
+        row[num_columns - 1] = rhs.clone();
+        // Express the new row in terms of the variables that are not basic...
+        for i in 0..fun_row {
+            if let Some(basic_column) = self.table.get_basic_var_of_row(i) {
+                let factor = row[basic_column].clone();
+                if factor != N::zero() {
+                    for (cell, basic_cell) in row.iter_mut().zip(&self.table.get_rows()[i]) {
+                        *cell -= factor.clone() * basic_cell.clone();
+                    }
+                }
+            }
+        }
+        // ... and keep its RHS non-negative.
+        let sign = if row[num_columns - 1] < N::zero() { -N::one() } else { N::one() };
+        if sign < N::zero() {
+            for cell in &mut row {
+                *cell = -cell.clone();
+            }
+        }
+        let new_row = fun_row;
+        self.table.insert_row(new_row, row);
+        // A surplus enters its row with -1 while the artificial marker of an equation is added
+        // after the row is negated.
+        let dual_sign = match rel {
+            Relationship::LEQ => N::one(),
+            Relationship::GEQ => -N::one(),
+            Relationship::EQ => sign.clone(),
+        };
+
+        // Pick a basic variable for the new row: the slack or surplus if it is positive there...
+        if rel != Relationship::EQ {
+            let marker_column = self.column_of(marker.name())?;
+            let entry = &self.table.get_rows()[new_row][marker_column];
+            if self.table.get_tolerances().is_positive(entry) {
+                self.table.set_basic_var_of_row(new_row, marker_column);
+                return Ok(Tag { columns, sign, dual_sign, rhs });
+            }
+        }
+        // ... otherwise an artificial variable that will not stay basic for long...
+        let arti_name = Name::from(format!("{}{}", "arti", id));
+        self.table.append_empty_column(arti_name.clone());
+        let arti_column = self.column_of(&arti_name)?;
+        self.table.set_column_kind(arti_column, ColumnKind::Artificial);
+        self.table.set_cell(new_row, arti_column, N::one());
+        self.table.set_basic_var_of_row(new_row, arti_column);
+        if rel != Relationship::EQ {
+            columns.push(arti_name.clone());
+        }
+        // ... unless an error variable can take its place...
+        for error_column in &error_columns {
+            let column = self.column_of(error_column)?;
+            if self.table.get_tolerances().is_positive(&self.table.get_rows()[new_row][column]) {
+                pivot_around(column, new_row, &mut self.table);
+                return Ok(Tag { columns, sign, dual_sign, rhs });
+            }
+        }
+        // ... so drive it out of the basis with a Phase I of its own.
+        self.run_phase_1_for(&arti_name, new_row, budget)?;
+        Ok(Tag { columns, sign, dual_sign, rhs })
+    }
+
+    fn run_phase_1_for(&mut self,
+                       arti_name: &Name,
+                       arti_row: usize,
+                       budget: &mut Budget)
+                       -> Result<(), SolveError<N>> {
+        let arti_column = self.column_of(arti_name)?;
+        let rhs_column = self.table.get_column_names().len() - 1;
+        if !self.table.get_tolerances().is_zero(&self.table.get_rows()[arti_row][rhs_column]) {
+            // Maximise W = -arti which, with arti substituted from its row, gives the W row.
+            let w_name = Name::from("W");
+            self.table.append_empty_column(w_name.clone());
+            let w_column = self.column_of(&w_name)?;
+            let mut w_row = self.table.get_rows()[arti_row]
+                .iter()
+                .map(|cell| -cell.clone())
+                .collect::<Vec<N>>();
+            w_row[arti_column] = N::zero();
+            w_row[w_column] = N::one();
+            self.table.append_row(w_row);
+            let w_row_index = self.table.get_rows().len() - 1;
+            self.table.set_basic_var_of_row(w_row_index, w_column);
+            self.table.set_num_fun_rows(2);
+            let phase1_solution = run_simplex(&mut self.table, &self.options, budget)
+                .and_then(|status| get_basic_solution_of(&self.table).map(|basic| (status, basic)));
+            self.table.remove_last_row();
+            self.table.set_num_fun_rows(1);
+            self.table.remove_column(&w_name);
+            let (status, phase1_solution) = phase1_solution?;
+            if !is_w_zero(&phase1_solution, self.table.get_tolerances()) {
+                return Err(limit_error(status).unwrap_or(SolveError::Infeasible));
+            }
+        }
+        // An artificial variable still basic at zero gives its place to any other variable in
+        // its row. If there is none the row is redundant and the artificial stays at zero.
+        if let Ok(row_index) = self.table.get_row_of_basic_var(arti_name) {
+            let excluded_columns = self.table.get_excluded_columns();
+            let rhs_column = self.table.get_column_names().len() - 1;
+            let enter_column = (0..rhs_column).find(|column| {
+                !excluded_columns.contains(column) &&
+                !self.table.get_tolerances().is_zero(&self.table.get_rows()[row_index][*column])
+            });
+            if let Some(column) = enter_column {
+                pivot_around(column, row_index, &mut self.table);
+            }
+        }
+        Ok(())
+    }
+
+    fn pivot_marker_into_basis(&mut self, marker: &Name) -> Result<usize, SolveError<N>> {
+        let num_constraint_rows = self.table.get_rows().len() - 1;
+        if let Ok(row_index) = self.table.get_row_of_basic_var(marker) {
+            if row_index < num_constraint_rows {
+                return Ok(row_index);
+            }
+        }
+        // Prefer the row that limits the marker the most as it enters so the other rows stay
+        // feasible. Failing that, the row where decreasing it hurts the least.
+        let marker_column = self.column_of(marker)?;
+        let rhs_column = self.table.get_column_names().len() - 1;
+        let mut leaving_positive: Option<(usize, N)> = None;
+        let mut leaving_negative: Option<(usize, N)> = None;
+        let tolerances = self.table.get_tolerances();
+        for (i, row) in self.table.get_rows().iter().enumerate().take(num_constraint_rows) {
+            let entry = row[marker_column].clone();
+            let (leaving, ratio) = if tolerances.is_positive(&entry) {
+                (&mut leaving_positive, row[rhs_column].clone() / entry)
+            } else if tolerances.is_negative(&entry) {
+                (&mut leaving_negative, row[rhs_column].clone() / -entry)
+            } else {
+                continue;
+            };
+            match *leaving {
+                Some((_, ref ratio_current)) if *ratio_current <= ratio => {}
+                _ => *leaving = Some((i, ratio)),
+            }
+        }
+        match leaving_positive.or(leaving_negative) {
+            Some((row_index, _)) => {
+                pivot_around(marker_column, row_index, &mut self.table);
+                Ok(row_index)
+            }
+            None => {
+                let reason = format!("marker \"{}\" is in no row of the table.", marker);
+                Err(SolveError::MalformedModel(reason))
+            }
+        }
+    }
+
+    fn column_of(&self, name: &Name) -> Result<usize, SolveError<N>> {
+        match self.table.get_column_names().get(name) {
+            Some(column) => Ok(*column),
+            None => {
+                let reason = format!("\"{}\" is not a column of the table.", name);
+                Err(SolveError::MalformedModel(reason))
+            }
+        }
+    }
+}
+
+// Handles from any registry but the solver's own stand for variables it knows nothing about.
+fn check_owned<'a, N, I>(variables: &Variables, vars: I, whose: &str) -> Result<(), SolveError<N>>
+    where N: Num + 'a,
+          I: IntoIterator<Item = &'a AbstVar<N>>
+{
+    for var in vars {
+        if let Some(handle) = var.name().handle() {
+            if !variables.owns(handle) {
+                let reason = format!("{} has a variable the solver did not hand out.", whose);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,93 +1,634 @@
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
 pub mod math;
 pub mod objective;
 pub mod tableau;
+pub mod errors;
+pub mod solutions;
+pub mod incremental;
+pub mod options;
+pub mod macros;
+pub mod format;
 
-use math::variables::is_gen_arti_var;
+use std::time::Instant;
+use math::variables::{AbstVar, Name, Variable, is_reserved_name};
+use math::expressions::Expression;
 use objective::problems::ProblemType;
 use objective::functions::Function;
-use objective::constraints::SystemOfConstraints;
-use objective::solvers::{transform_constraint_rels_to_eq, rearrange_fun_eq_zero};
-use tableau::tables::Table;
+use objective::constraints::{Constraint, SystemOfConstraints};
+use objective::solvers::{transform_constraint_rels_to_eq, rearrange_fun_eq_zero, add_error_vars,
+                         get_dual_markers};
+use tableau::tables::{Table, ColumnKind};
 use tableau::initials::{get_initial_table_from, append_function};
-use tableau::enter_vars::{enter_var_pivot_optimal, enter_var_pivot_feasible};
-use tableau::leave_vars::leave_var;
+use tableau::enter_vars::enter_var_dual;
+use tableau::leave_vars::{Step, leave_var_bounded, leave_var_dual, get_unbounded_ray};
 use tableau::pivots::{pivot_around, apply_transition_rule};
+use tableau::ranges::{get_cost_delta_range, get_rhs_delta_range};
+use tableau::tolerances::Tolerances;
+use tableau::pivot_rules::{PivotRule, Bland};
+use errors::SolveError;
+use solutions::{Solution, Status, Sensitivity, Range};
+use options::{Options, CancellationToken};
+
+pub use math::numbers::Num;
 
-pub type Num = f32;
+// The name, lower bound and any upper bound of each bounded variable.
+type Bounds<N> = Vec<(Name, N, Option<N>)>;
+
+pub fn optimise<N: Num>(function: &mut Function<N>,
+                        constraints: &mut SystemOfConstraints<N>)
+                        -> Result<Solution<N>, SolveError<N>> {
+    optimise_with(function, constraints, &Options::default())
+}
 
-pub fn optimise(function: &mut Function,
-                constraints: &mut SystemOfConstraints)
-                -> Vec<(String, Num)> {
+pub fn optimise_with<N: Num>(function: &mut Function<N>,
+                             constraints: &mut SystemOfConstraints<N>,
+                             options: &Options<N>)
+                             -> Result<Solution<N>, SolveError<N>> {
+    check_model(function, constraints)?;
+    let bounds = get_bounds(constraints)?;
     rearrange_fun_eq_zero(function);
+    add_error_vars(function, constraints);
+    // Count every variable up from its lower bound before the signs of the RHS decide how the
+    // constraints are transformed, and put the model back once it is solved.
+    let shifts = shift_lower_bounds(function, constraints, &bounds, N::one());
+    let mut dual_markers = get_dual_markers(constraints);
+    for marker in &mut dual_markers {
+        marker.3 -= shifts[marker.0 - 1].clone();
+    }
+    let result = solve_shifted(function, constraints, options, &bounds, &dual_markers);
+    shift_lower_bounds(function, constraints, &bounds, -N::one());
+    result
+}
+
+fn solve_shifted<N: Num>(function: &Function<N>,
+                         constraints: &mut SystemOfConstraints<N>,
+                         options: &Options<N>,
+                         bounds: &[(Name, N, Option<N>)],
+                         dual_markers: &[(usize, Name, N, N)])
+                         -> Result<Solution<N>, SolveError<N>> {
     if let Some(mut phase1_fun) = transform_constraint_rels_to_eq(constraints) {
         rearrange_fun_eq_zero(&mut phase1_fun);
         let mut phase1_table = get_initial_table_from(function, constraints);
+        phase1_table.set_tolerances(options.tolerances().clone());
         // Set Phase I function to work with.
         append_function(&phase1_fun, &mut phase1_table);
-        let phase1_solution = run_simplex(&phase1_fun, &mut phase1_table);
-        if phase1_solution.contains(&("W".to_string(), 0.0)) {
+        set_bounds(&mut phase1_table, bounds);
+        let mut budget = Budget::new(options);
+        let phase1_status = run_simplex(&mut phase1_table, options, &mut budget)?;
+        let phase1_solution = get_basic_solution_of(&phase1_table)?;
+        // Phase I may have run out before it finished, but once "W" is zero there is a feasible
+        // basis to carry on from.
+        if is_w_zero(&phase1_solution, phase1_table.get_tolerances()) {
             // Check to see if there are any artificial variables in the Phase I solution.
             let arti_vars_in_solution = phase1_solution.into_iter()
-                .filter(|basic_var| is_gen_arti_var(&basic_var.0))
-                .collect::<Vec<(String, Num)>>();
-            if arti_vars_in_solution.is_empty() {
-                // Carry out Phase II - no need for Transition Rule.
-                return run_phase_2_from_1(function, &mut phase1_table);
-            } else {
+                .filter(|basic_var| {
+                    let column = phase1_table.get_column_names()[&basic_var.0];
+                    phase1_table.get_column_kind(column) == ColumnKind::Artificial
+                })
+                .collect::<Vec<(Name, N)>>();
+            if !arti_vars_in_solution.is_empty() {
                 // Remove artificial variables from the basis by applying the Transition Rule.
-                apply_transition_rule(arti_vars_in_solution, constraints, &mut phase1_table);
-                return run_phase_2_from_1(function, &mut phase1_table);
+                apply_transition_rule(arti_vars_in_solution, constraints, &mut phase1_table)?;
             }
+            let status = run_phase_2_from_1(&mut phase1_table, options, &mut budget)?;
+            get_solution(function, &phase1_table, budget.pivots, dual_markers, status)
         } else {
-            panic!("Could not find a feasible solution to start Phase II.");
+            Err(limit_error(phase1_status).unwrap_or(SolveError::Infeasible))
         }
     } else {
         // Carry on with Phase II.
         let mut table = get_initial_table_from(function, constraints);
-        return run_simplex(function, &mut table);
+        table.set_tolerances(options.tolerances().clone());
+        set_bounds(&mut table, bounds);
+        let mut budget = Budget::new(options);
+        let status = run_simplex(&mut table, options, &mut budget)?;
+        get_solution(function, &table, budget.pivots, dual_markers, status)
+    }
+}
+
+fn check_model<N: Num>(function: &Function<N>,
+                       constraints: &SystemOfConstraints<N>)
+                       -> Result<(), SolveError<N>> {
+    check_function(function)?;
+    let mut first_handle = None;
+    let function_vars = function.exp().lhs().iter().chain(function.exp().rhs());
+    check_handles(function_vars, &mut first_handle, "function")?;
+    for (i, constraint) in constraints.system().iter().enumerate() {
+        check_constraint(constraint, i + 1)?;
+        let whose = format!("constraint {}", i + 1);
+        check_handles(get_constraint_vars(constraint), &mut first_handle, &whose)?;
+        if let Constraint::Bounded(ref var, ..) = *constraint {
+            if *var.name() == function.name() {
+                let reason = format!("constraint {} must not bound the function variable.", i + 1);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_function<N: Num>(function: &Function<N>) -> Result<(), SolveError<N>> {
+    match function.exp().lhs().first() {
+        Some(&AbstVar::Variable { .. }) if function.exp().lhs().len() == 1 => {}
+        _ => {
+            let reason = "function must have a single variable on its left hand side.";
+            return Err(SolveError::MalformedModel(reason.to_string()));
+        }
+    }
+    check_names(function.exp().lhs().iter().chain(function.exp().rhs()), "function")
+}
+
+// Names that read like handles would be mistaken for them in solutions and model files.
+fn check_names<'a, N, I>(vars: I, whose: &str) -> Result<(), SolveError<N>>
+    where N: Num + 'a,
+          I: IntoIterator<Item = &'a AbstVar<N>>
+{
+    for var in vars {
+        if let AbstVar::Variable { name: Name::Text(ref name), .. } = *var {
+            if is_reserved_name(name) {
+                let reason = format!("{} has a variable named \"{}\", which reads like a \
+                                      variable handle.",
+                                     whose,
+                                     name);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_constraint<N: Num>(constraint: &Constraint<N>, id: usize) -> Result<(), SolveError<N>> {
+    if let Constraint::Stay(ref var) = *constraint {
+        match *var {
+            AbstVar::Variable { .. } => {}
+            _ => {
+                let reason = format!("constraint {} must stay on a variable.", id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    if let Constraint::Bounded(ref var, ..) = *constraint {
+        match *var {
+            AbstVar::Variable { .. } => {}
+            _ => {
+                let reason = format!("constraint {} must bound a variable.", id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    if let Constraint::Weighted(_, ref strength) = *constraint {
+        match strength.weight() {
+            Some(weight) if weight < N::zero() => {
+                let reason = format!("constraint {} must not have a negative weight.", id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+            _ => {}
+        }
+    }
+    if let Constraint::Regular(ref exp) | Constraint::Weighted(ref exp, _) = *constraint {
+        let lhs_reason = format!("constraint {} must only have variables on its left hand side.",
+                                 id);
+        if exp.lhs().is_empty() {
+            return Err(SolveError::MalformedModel(lhs_reason));
+        }
+        for var in exp.lhs() {
+            match *var {
+                AbstVar::Variable { .. } => {}
+                _ => return Err(SolveError::MalformedModel(lhs_reason)),
+            }
+        }
+        match exp.rhs().first() {
+            Some(&AbstVar::Constant { .. }) if exp.rhs().len() == 1 => {}
+            _ => {
+                let reason = format!("constraint {} must have a single constant on its right \
+                                      hand side.",
+                                     id);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        }
+    }
+    check_names(get_constraint_vars(constraint), &format!("constraint {}", id))
+}
+
+// A handle only stands for a variable next to others from the registry that handed it out.
+fn check_handles<'a, N, I>(vars: I,
+                           first_handle: &mut Option<Variable>,
+                           whose: &str)
+                           -> Result<(), SolveError<N>>
+    where N: Num + 'a,
+          I: IntoIterator<Item = &'a AbstVar<N>>
+{
+    for handle in vars.into_iter().filter_map(|var| var.name().handle()) {
+        match *first_handle {
+            Some(ref first) if !first.shares_owner(&handle) => {
+                let reason = format!("{} has a variable from another registry than the rest of \
+                                      the model.",
+                                     whose);
+                return Err(SolveError::MalformedModel(reason));
+            }
+            Some(_) => {}
+            None => *first_handle = Some(handle),
+        }
+    }
+    Ok(())
+}
+
+fn get_constraint_vars<N: Num>(constraint: &Constraint<N>) -> Vec<&AbstVar<N>> {
+    match *constraint {
+        Constraint::Regular(ref exp) |
+        Constraint::Weighted(ref exp, _) => exp.lhs().iter().chain(exp.rhs()).collect(),
+        Constraint::NonNegative(ref var) |
+        Constraint::Bounded(ref var, ..) |
+        Constraint::Stay(ref var) => vec![var],
     }
 }
 
-fn run_simplex(function: &Function, table: &mut Table) -> Vec<(String, Num)> {
+// The lower and any upper bound of every bounded variable, the tightest of them where a
+// variable has several.
+fn get_bounds<N: Num>(constraints: &SystemOfConstraints<N>)
+                      -> Result<Bounds<N>, SolveError<N>> {
+    let mut bounds: Bounds<N> = Vec::new();
+    for constraint in constraints.system() {
+        let (var, lower, upper) = match *constraint {
+            Constraint::NonNegative(ref var) => (var, N::zero(), None),
+            Constraint::Bounded(ref var, ref lower, ref upper) => {
+                (var, lower.clone(), upper.clone())
+            }
+            _ => continue,
+        };
+        let bound = match bounds.iter_mut().find(|bound| bound.0 == *var.name()) {
+            Some(bound) => bound,
+            None => {
+                bounds.push((var.name().clone(), lower, upper));
+                continue;
+            }
+        };
+        if lower > bound.1 {
+            bound.1 = lower;
+        }
+        if let Some(upper) = upper {
+            if bound.2.as_ref().is_none_or(|current| upper < *current) {
+                bound.2 = Some(upper);
+            }
+        }
+    }
+    // No point lies between bounds that cross.
+    if bounds.iter().any(|bound| bound.2.as_ref().is_some_and(|upper| *upper < bound.1)) {
+        return Err(SolveError::Infeasible);
+    }
+    Ok(bounds)
+}
+
+// Moves the lower bounds of the variables into the constants, so that the table counts every
+// variable up from its lower bound rather than from zero, or back again with a sign of -1.
+// Returns how far the constant of each constraint moved.
+fn shift_lower_bounds<N: Num>(function: &mut Function<N>,
+                              constraints: &mut SystemOfConstraints<N>,
+                              bounds: &[(Name, N, Option<N>)],
+                              sign: N)
+                              -> Vec<N> {
+    let shifts = constraints.system_mut()
+        .iter_mut()
+        .map(|constraint| match *constraint {
+            Constraint::Regular(ref mut exp) |
+            Constraint::Weighted(ref mut exp, _) => shift_constant(exp, bounds, &sign),
+            _ => N::zero(),
+        })
+        .collect();
+    shift_constant(function.exp_max_mut(), bounds, &sign);
+    shifts
+}
+
+fn shift_constant<N: Num>(exp: &mut Expression<N>,
+                          bounds: &[(Name, N, Option<N>)],
+                          sign: &N)
+                          -> N {
+    let mut shift = N::zero();
+    for var in exp.lhs() {
+        if let AbstVar::Variable { ref name, ref coefficient } = *var {
+            if let Some(bound) = bounds.iter().find(|bound| bound.0 == *name) {
+                shift -= sign.clone() * coefficient.clone() * bound.1.clone();
+            }
+        }
+    }
+    if shift != N::zero() {
+        let name = exp.rhs()[0].name().clone();
+        exp.add_rhs(AbstVar::Constant {
+                        name,
+                        value: shift.clone(),
+                    });
+    }
+    shift
+}
+
+// Hands the bounds over to a table built from the shifted model.
+fn set_bounds<N: Num>(table: &mut Table<N>, bounds: &[(Name, N, Option<N>)]) {
+    for (name, lower, upper) in bounds {
+        if let Some(&column) = table.get_column_names().get(name) {
+            table.set_bound(column, lower.clone(), upper.clone());
+        }
+    }
+}
+
+// How many pivots a solve has taken and how far it may go before it has to stop.
+struct Budget {
+    pivots: usize,
+    iteration_limit: Option<usize>,
+    deadline: Option<Instant>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl Budget {
+    fn new<N: Num>(options: &Options<N>) -> Budget {
+        Budget {
+            pivots: 0,
+            iteration_limit: options.iteration_limit(),
+            deadline: options.time_limit().map(|limit| Instant::now() + limit),
+            cancellation_token: options.cancellation_token().cloned(),
+        }
+    }
+
+    // The status a solve stops with once it has used up its budget.
+    fn check(&self) -> Option<Status> {
+        if self.cancellation_token.as_ref().is_some_and(|token| token.is_cancelled()) {
+            Some(Status::Cancelled)
+        } else if self.iteration_limit.is_some_and(|limit| self.pivots >= limit) {
+            Some(Status::IterationLimit)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(Status::TimeLimit)
+        } else {
+            None
+        }
+    }
+}
+
+fn limit_error<N>(status: Status) -> Option<SolveError<N>> {
+    match status {
+        Status::IterationLimit => Some(SolveError::IterationLimit),
+        Status::TimeLimit => Some(SolveError::TimeLimit),
+        Status::Cancelled => Some(SolveError::Cancelled),
+        _ => None,
+    }
+}
+
+fn run_simplex<N: Num>(table: &mut Table<N>,
+                       options: &Options<N>,
+                       budget: &mut Budget)
+                       -> Result<Status, SolveError<N>> {
+    let mut pivot_rule = options.pivot_rule().box_clone();
+    let mut bland = Bland;
+    let mut degenerate_pivots = 0;
+    // Pivot until the table is optimal or the budget runs out and report which it was.
     loop {
-        match table.get_basic_solution() {
-            Ok(mut basic_solution) => {
-                if table.is_solution_optimal() {
-                    if function.p_type() == &ProblemType::MIN {
-                        // Give solution for MIN as currently it is given as MAX.
-                        let (pos, _) =
-                            basic_solution.iter()
-                                .enumerate()
-                                .find(|&entry| (entry.1).0 == "Q")
-                                .expect("Failed to locate value of \"Q\" in optimal solution.");
-                        basic_solution[pos] = (function.name().clone(),
-                                               basic_solution[pos].1 * -1.0);
-                        return basic_solution;
-                    } else {
-                        return basic_solution;
-                    }
-                } else {
-                    let enter_var_index = enter_var_pivot_optimal(&table);
-                    pivot_around(enter_var_index, leave_var(enter_var_index, &table), table);
+        // Bland's rule can not cycle, so it takes over once pivots stop getting anywhere.
+        let rule: &mut dyn PivotRule<N> = match options.degenerate_pivot_limit() {
+            Some(limit) if degenerate_pivots >= limit => &mut bland,
+            _ => &mut *pivot_rule,
+        };
+        let function_value = get_function_value(table);
+        if let Err((row, column)) = table.get_basic_solution() {
+            // Phase I starts every row off feasible and no pivot takes it out of that.
+            let reason = format!("table has a negative basic variable in row {}, column {}.",
+                                 row,
+                                 column);
+            return Err(SolveError::MalformedModel(reason));
+        }
+        if table.is_solution_optimal()? {
+            return Ok(Status::Optimal);
+        }
+        if let Some(status) = budget.check() {
+            return Ok(status);
+        }
+        let enter_var_index = match rule.enter_var(table) {
+            Some(index) => index,
+            None => return Ok(Status::Optimal),
+        };
+        // The chosen rule keeps track of every pivot, even those Bland's rule picks for it.
+        if let Some(row) = pivot_or_report_unbounded(enter_var_index, rule, table)? {
+            pivot_rule.pivoted(table, row, enter_var_index);
+        }
+        budget.pivots += 1;
+        if table.get_tolerances().is_zero(&(get_function_value(table) - function_value)) {
+            degenerate_pivots += 1;
+        } else {
+            degenerate_pivots = 0;
+        }
+    }
+}
+
+fn get_function_value<N: Num>(table: &Table<N>) -> N {
+    let rhs_column = table.get_column_names().len() - 1;
+    table.get_rows()[table.get_rows().len() - 1][rhs_column].clone()
+}
+
+fn get_basic_solution_of<N: Num>(table: &Table<N>) -> Result<Vec<(Name, N)>, SolveError<N>> {
+    match table.get_basic_solution() {
+        Ok(basic_solution) => Ok(basic_solution),
+        Err((row, column)) => {
+            let reason = format!("optimal table has a negative basic variable in row {}, column \
+                                  {}.",
+                                 row,
+                                 column);
+            Err(SolveError::MalformedModel(reason))
+        }
+    }
+}
+
+fn is_w_zero<N: Num>(phase1_solution: &[(Name, N)], tolerances: &Tolerances<N>) -> bool {
+    // Phase I found a feasible point only if it drove "W" all the way to zero.
+    phase1_solution.iter().any(|(name, value)| name == "W" && tolerances.is_zero(value))
+}
+
+fn get_solution<N: Num>(function: &Function<N>,
+                        table: &Table<N>,
+                        iterations: usize,
+                        dual_markers: &[(usize, Name, N, N)],
+                        status: Status)
+                        -> Result<Solution<N>, SolveError<N>> {
+    // A function to minimise is solved as "Q" = -function to maximise.
+    let (function_column, sign) = match *function.p_type() {
+        ProblemType::MIN => (Name::from("Q"), -N::one()),
+        ProblemType::MAX => (function.name(), N::one()),
+    };
+    if !table.get_column_names().contains_key(&function_column) {
+        let reason = format!("failed to locate value of \"{}\" in optimal solution.",
+                             function_column);
+        return Err(SolveError::MalformedModel(reason));
+    }
+    let rhs_column = table.get_column_names().len() - 1;
+    let table_rows = table.get_rows();
+    let function_row = &table_rows[table_rows.len() - 1];
+    // Read the value of every basic variable off its row. Going by row rather than by column
+    // means two identical unit columns can not both claim the same row.
+    let mut basic_rows = vec![None; rhs_column];
+    for (i, row) in table_rows.iter().enumerate().take(table_rows.len() - 1) {
+        if let Some(column) = table.get_basic_var_of_row(i) {
+            if table.get_tolerances().is_negative(&row[rhs_column]) {
+                let reason = format!("optimal table has a negative basic variable in row {}.", i);
+                return Err(SolveError::MalformedModel(reason));
+            }
+            basic_rows[column] = Some(i);
+        }
+    }
+    let excluded_columns = table.get_excluded_columns();
+    let function_column_index = table.get_column_names()[&function_column];
+    let non_basic_columns = (0..rhs_column)
+        .filter(|column| {
+            basic_rows[*column].is_none() && *column != function_column_index &&
+            !excluded_columns.contains(column)
+        })
+        .collect::<Vec<usize>>();
+    let mut columns = table.get_column_names()
+        .iter()
+        .filter(|&(_, index)| *index != rhs_column)
+        .collect::<Vec<(&Name, &usize)>>();
+    columns.sort_by_key(|&(_, index)| *index);
+    let mut values = Vec::new();
+    let mut reduced_costs = Vec::new();
+    let mut cost_ranges = Vec::new();
+    let mut internals = Vec::new();
+    let mut status = status;
+    for (name, &column) in columns {
+        if *name == function_column {
+            continue;
+        }
+        // A non-basic variable that costs nothing to bring in leads to another optimum.
+        if status == Status::Optimal && non_basic_columns.contains(&column) &&
+           table.get_tolerances().is_zero_cost(&function_row[column]) {
+            status = Status::AlternativeOptima;
+        }
+        let value = table.get_value_of(column,
+                                       basic_rows[column]
+                                           .map_or(N::zero(),
+                                                   |row| table_rows[row][rhs_column].clone()));
+        // A complemented column counts its variable down from the upper bound, so its reduced
+        // cost and cost range point the other way.
+        let direction = if table.is_complemented(column) { -N::one() } else { N::one() };
+        if table.get_column_kind(column) != ColumnKind::Decision {
+            internals.push((name.clone(), value));
+        } else {
+            values.push((name.clone(), value));
+            // The function row holds by how much the function to maximise would drop.
+            let reduced_cost = match basic_rows[column] {
+                Some(_) => N::zero(),
+                None => -direction.clone() * sign.clone() * function_row[column].clone(),
+            };
+            reduced_costs.push((name.clone(), reduced_cost));
+            // The coefficient in the function as given, read back from the maximised form.
+            let cost = -sign.clone() *
+                       function.exp_max()
+                .lhs()
+                .iter()
+                .filter(|var| var.name() == name)
+                .fold(N::zero(), |sum, var| sum + var.get_data());
+            let (lower, upper) =
+                get_cost_delta_range(column, basic_rows[column], &non_basic_columns, table);
+            let (lower, upper) = if direction < N::zero() {
+                (upper.map(|upper| -upper), lower.map(|lower| -lower))
+            } else {
+                (lower, upper)
+            };
+            let cost_range = match *function.p_type() {
+                ProblemType::MAX => {
+                    Range::new(lower.map(|lower| cost.clone() + lower),
+                               upper.map(|upper| cost.clone() + upper))
+                }
+                ProblemType::MIN => {
+                    Range::new(upper.map(|upper| cost.clone() - upper),
+                               lower.map(|lower| cost.clone() - lower))
                 }
+            };
+            cost_ranges.push((name.clone(), cost_range));
+        }
+    }
+    let objective = (function.name(), sign.clone() * function_row[rhs_column].clone());
+    // The function row holds c_B B^-1 under each marker column, which is how much the
+    // function to maximise gains per unit of RHS.
+    let mut duals = Vec::with_capacity(dual_markers.len());
+    let mut rhs_ranges = Vec::with_capacity(dual_markers.len());
+    for &(constraint, ref marker, ref marker_sign, ref rhs) in dual_markers {
+        match table.get_column_names().get(marker) {
+            Some(&column) => {
+                duals.push((constraint,
+                            sign.clone() * marker_sign.clone() * function_row[column].clone()));
+                let (lower, upper) = get_rhs_delta_range(column, marker_sign.clone(), table);
+                let rhs_range = Range::new(lower.map(|lower| rhs.clone() + lower),
+                                           upper.map(|upper| rhs.clone() + upper));
+                rhs_ranges.push((constraint, rhs_range));
+            }
+            None => {
+                let reason = format!("\"{}\" is not a column of the table.", marker);
+                return Err(SolveError::MalformedModel(reason));
             }
-            Err(index_report) => {
-                let enter_var_index = enter_var_pivot_feasible(&table,
-                                                               index_report.0,
-                                                               index_report.1)
-                                                               .expect("Could not find a leftmost \
-                                                               positive value cell for pivoting \
-                                                               to enter feasible region.");
-                pivot_around(enter_var_index, leave_var(enter_var_index, &table), table);
+        }
+    }
+    let sensitivity = Sensitivity::new(reduced_costs, duals, cost_ranges, rhs_ranges);
+    Ok(Solution::new(values, internals, objective, sensitivity, status, iterations))
+}
+
+// Takes the step the bounds allow and returns the row it pivoted on, if it did not just flip the
+// entering variable to its other bound.
+fn pivot_or_report_unbounded<N: Num>(enter_var_index: usize,
+                                     pivot_rule: &mut dyn PivotRule<N>,
+                                     table: &mut Table<N>)
+                                     -> Result<Option<usize>, SolveError<N>> {
+    let rule_row = pivot_rule.leave_var(enter_var_index, table);
+    match leave_var_bounded(enter_var_index, rule_row, table) {
+        Some(Step::Pivot(leave_var_index)) => {
+            pivot_around(enter_var_index, leave_var_index, table);
+            Ok(Some(leave_var_index))
+        }
+        Some(Step::PivotAtUpper(leave_var_index)) => {
+            // Count the leaving variable down from its upper bound so it leaves at zero.
+            if let Some(column) = table.get_basic_var_of_row(leave_var_index) {
+                table.complement_column(column);
             }
+            pivot_around(enter_var_index, leave_var_index, table);
+            Ok(Some(leave_var_index))
+        }
+        Some(Step::Flip) => {
+            table.complement_column(enter_var_index);
+            Ok(None)
         }
+        None => Err(SolveError::Unbounded(get_unbounded_ray(enter_var_index, table))),
     }
 }
 
-fn run_phase_2_from_1(fun: &Function, table: &mut Table) -> Vec<(String, Num)> {
+fn run_dual_simplex<N: Num>(table: &mut Table<N>,
+                            budget: &mut Budget)
+                            -> Result<Status, SolveError<N>> {
+    // Only RHS values changed since the last optimum so the function row is still non-negative;
+    // pivot the negative basic variables out while keeping it that way.
+    loop {
+        let leave_var_index = match leave_var_dual(table) {
+            Some(index) => index,
+            None => return Ok(Status::Optimal),
+        };
+        if let Some(status) = budget.check() {
+            return Ok(status);
+        }
+        match enter_var_dual(leave_var_index, table) {
+            Some(enter_var_index) => pivot_around(enter_var_index, leave_var_index, table),
+            None => return Err(SolveError::Infeasible),
+        }
+        budget.pivots += 1;
+    }
+}
+
+fn run_phase_2_from_1<N: Num>(table: &mut Table<N>,
+                              options: &Options<N>,
+                              budget: &mut Budget)
+                              -> Result<Status, SolveError<N>> {
     // Set original function to work with.
-    table.remove_last_row();
+    if table.remove_last_row().is_none() {
+        let reason = "Phase I table has no function row to remove.";
+        return Err(SolveError::MalformedModel(reason.to_string()));
+    }
+    table.remove_column(&Name::from("W"));
     let old_num_fun_rows = table.get_num_fun_rows();
     table.set_num_fun_rows(old_num_fun_rows - 1);
-    run_simplex(fun, table)
+    run_simplex(table, options, budget)
 }
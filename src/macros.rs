@@ -0,0 +1,184 @@
+use Num;
+
+/// Writes a model the way it reads on paper and gives back its function and constraints:
+///
+/// ```ignore
+/// let (mut function, mut constraints) = lp!(maximize Z = 6 x1 + 14 x2;
+///                                           0.5 x1 + 2 x2 <= 24;
+///                                           x1, x2 >= 0);
+/// ```
+///
+/// Variables are named by their identifiers and coefficients go in front of them, space
+/// separated. Numbers are `f64` unless the model starts with another type, as in `lp!(<f32> ...)`.
+#[macro_export]
+macro_rules! lp {
+    (< $n:ty > $($rest:tt)*) => {
+        lp!(@model $n; $($rest)*)
+    };
+    (maximize $($rest:tt)*) => {
+        lp!(@model f64; maximize $($rest)*)
+    };
+    (maximise $($rest:tt)*) => {
+        lp!(@model f64; maximise $($rest)*)
+    };
+    (minimize $($rest:tt)*) => {
+        lp!(@model f64; minimize $($rest)*)
+    };
+    (minimise $($rest:tt)*) => {
+        lp!(@model f64; minimise $($rest)*)
+    };
+    (@model $n:ty; maximize $name:ident = $($rest:tt)*) => {
+        lp!(@terms objective {MAX, $name} $n; []; $($rest)* ;)
+    };
+    (@model $n:ty; maximise $name:ident = $($rest:tt)*) => {
+        lp!(@terms objective {MAX, $name} $n; []; $($rest)* ;)
+    };
+    (@model $n:ty; minimize $name:ident = $($rest:tt)*) => {
+        lp!(@terms objective {MIN, $name} $n; []; $($rest)* ;)
+    };
+    (@model $n:ty; minimise $name:ident = $($rest:tt)*) => {
+        lp!(@terms objective {MIN, $name} $n; []; $($rest)* ;)
+    };
+
+    // The function, once its terms are in.
+    (@objective {$p_type:ident, $name:ident} $n:ty; [$($t:expr,)+]; ; $($rest:tt)*) => {
+        ($crate::objective::functions::Function::new(
+             $crate::math::expressions::Expression::new(
+                 vec![lp!(@term $n; 1; $name)],
+                 $crate::math::relationships::Relationship::EQ,
+                 vec![$($t,)+]),
+             $crate::objective::problems::ProblemType::$p_type),
+         lp!(@constraint {[]} $n; $($rest)*))
+    };
+    (@objective $state:tt $n:ty; [$($t:expr,)*]; $($rest:tt)*) => {
+        lp!(@expected_terms_then_semicolon $($rest)*)
+    };
+
+    // The start of a constraint.
+    (@constraint {[$($c:expr,)*]} $n:ty; ) => {
+        $crate::objective::constraints::SystemOfConstraints::new(vec![$($c,)*])
+    };
+    (@constraint $state:tt $n:ty; ; $($rest:tt)*) => {
+        lp!(@constraint $state $n; $($rest)*)
+    };
+    (@constraint {[$($c:expr,)*]} $n:ty; $($v:ident),+ >= 0; $($rest:tt)*) => {
+        lp!(@constraint {[$($c,)*
+                          $($crate::objective::constraints::new_non_neg_con(
+                              lp!(@term $n; 1; $v)),)+]}
+            $n; $($rest)*)
+    };
+    (@constraint $state:tt $n:ty; $($rest:tt)*) => {
+        lp!(@terms relation $state $n; []; $($rest)*)
+    };
+
+    // The relationship and constant that end a constraint, once its terms are in.
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; <= $rhs:literal; $($rest:tt)*) => {
+        lp!(@push $state $n; [$($t,)+]; LEQ; $rhs; $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; >= $rhs:literal; $($rest:tt)*) => {
+        lp!(@push $state $n; [$($t,)+]; GEQ; $rhs; $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; = $rhs:literal; $($rest:tt)*) => {
+        lp!(@push $state $n; [$($t,)+]; EQ; $rhs; $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; <= $($rest:tt)*) => {
+        lp!(@expected_constant $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; >= $($rest:tt)*) => {
+        lp!(@expected_constant $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)+]; = $($rest:tt)*) => {
+        lp!(@expected_constant $($rest)*)
+    };
+    (@relation $state:tt $n:ty; [$($t:expr,)*]; $($rest:tt)*) => {
+        lp!(@expected_terms_then_relationship $($rest)*)
+    };
+    (@push {[$($c:expr,)*]} $n:ty; [$($t:expr,)+]; $rel:ident; $rhs:literal; $($rest:tt)*) => {
+        lp!(@constraint {[$($c,)*
+                          $crate::objective::constraints::new_reg_con(
+                              $crate::math::expressions::Expression::new(
+                                  vec![$($t,)+],
+                                  $crate::math::relationships::Relationship::$rel,
+                                  vec![$crate::math::variables::new_const(
+                                      "RHS", $crate::macros::coefficient::<$n, _>($rhs))])),]}
+            $n; $($rest)*)
+    };
+
+    // Terms, each with an optional sign and coefficient, up to whatever comes after them.
+    (@terms $k:ident $state:tt $n:ty; [$($t:expr,)*]; + $c:literal $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [$($t,)* lp!(@term $n; $c; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; [$($t:expr,)*]; - $c:literal $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [$($t,)* lp!(@term $n; -$c; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; [$($t:expr,)*]; + $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [$($t,)* lp!(@term $n; 1; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; [$($t:expr,)*]; - $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [$($t,)* lp!(@term $n; -1; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; []; $c:literal $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [lp!(@term $n; $c; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; []; $v:ident $($rest:tt)*) => {
+        lp!(@terms $k $state $n; [lp!(@term $n; 1; $v),]; $($rest)*)
+    };
+    (@terms $k:ident $state:tt $n:ty; [$($t:expr,)*]; $($rest:tt)*) => {
+        lp!(@$k $state $n; [$($t,)*]; $($rest)*)
+    };
+    (@term $n:ty; $c:expr; $v:ident) => {
+        $crate::math::variables::new_var(stringify!($v), $crate::macros::coefficient::<$n, _>($c))
+    };
+
+    // Anything else is a mistake in the model; naming no rule for it points the error at the
+    // token that caused it.
+    (@expected_terms_then_semicolon) => {};
+    (@expected_terms_then_relationship) => {};
+    (@expected_constant) => {};
+}
+
+/// Writes constraints the way they read on paper, as `lp!` does after its function, and gives
+/// back the `SystemOfConstraints` they make up:
+///
+/// ```ignore
+/// let mut subject_to = constraints!(0.5 x1 + 2 x2 <= 24; x1 - x2 = 3; x1, x2 >= 0);
+/// ```
+#[macro_export]
+macro_rules! constraints {
+    (< $n:ty > $($rest:tt)*) => {
+        lp!(@constraint {[]} $n; $($rest)* ;)
+    };
+    ($($rest:tt)*) => {
+        lp!(@constraint {[]} f64; $($rest)* ;)
+    };
+}
+
+/// The numbers the macros accept as coefficients: integer literals for any type of number and
+/// float literals for the floating point ones.
+#[doc(hidden)]
+pub trait Coefficient<N> {
+    fn coefficient(self) -> N;
+}
+
+impl<N: Num> Coefficient<N> for i32 {
+    fn coefficient(self) -> N {
+        N::from_i32(self)
+    }
+}
+
+impl Coefficient<f64> for f64 {
+    fn coefficient(self) -> f64 {
+        self
+    }
+}
+
+impl Coefficient<f32> for f64 {
+    fn coefficient(self) -> f32 {
+        self as f32
+    }
+}
+
+#[doc(hidden)]
+pub fn coefficient<N, C: Coefficient<N>>(c: C) -> N {
+    c.coefficient()
+}
@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+
+/// A signed integer of any size, kept as base 2^32 digits with the least significant first.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct BigInt {
+    // Zero has no digits and is never negative, and the last digit is never zero, so equal
+    // values always compare equal field by field.
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt {
+            negative: false,
+            digits: Vec::new(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_digits(false, self.digits.clone())
+    }
+
+    /// The greatest common divisor of both magnitudes, zero only when both are zero.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        BigInt::from_digits(false, gcd_digits(&self.digits, &other.digits))
+    }
+
+    /// Quotient rounded towards zero and the remainder, which takes the sign of `self`.
+    pub fn div_rem(&self, other: &BigInt) -> (BigInt, BigInt) {
+        if other.is_zero() {
+            panic!("BigInt division by zero.");
+        }
+        let (quotient, remainder) = div_rem_digits(&self.digits, &other.digits);
+        (BigInt::from_digits(self.negative != other.negative, quotient),
+         BigInt::from_digits(self.negative, remainder))
+    }
+
+    fn from_digits(negative: bool, digits: Vec<u32>) -> BigInt {
+        let digits = trim(digits);
+        BigInt {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> BigInt {
+        let magnitude = n.unsigned_abs();
+        BigInt::from_digits(n < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_digits(self.negative, add_digits(&self.digits, &other.digits));
+        }
+        // Opposite signs: take the smaller magnitude from the larger one.
+        match cmp_digits(&self.digits, &other.digits) {
+            Ordering::Less => {
+                BigInt::from_digits(other.negative, sub_digits(&other.digits, &self.digits))
+            }
+            _ => BigInt::from_digits(self.negative, sub_digits(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_digits(self.negative != other.negative,
+                            mul_digits(&self.digits, &other.digits))
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_digits(!self.negative, self.digits.clone())
+    }
+}
+
+macro_rules! forward_owned_binop {
+    ($imp:ident, $method:ident) => {
+        impl $imp for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: BigInt) -> BigInt {
+                (&self).$method(&other)
+            }
+        }
+    }
+}
+
+forward_owned_binop!(Add, add);
+forward_owned_binop!(Sub, sub);
+forward_owned_binop!(Mul, mul);
+forward_owned_binop!(Div, div);
+forward_owned_binop!(Rem, rem);
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_digits(&self.digits, &other.digits),
+            (true, true) => cmp_digits(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Peel off nine decimal digits at a time, least significant first.
+        let mut chunks = Vec::new();
+        let mut digits = self.digits.clone();
+        while !digits.is_empty() {
+            let (quotient, remainder) = div_rem_digits(&digits, &[1_000_000_000]);
+            chunks.push(remainder.first().cloned().unwrap_or(0));
+            digits = quotient;
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks[chunks.len() - 1])?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+fn trim(mut digits: Vec<u32>) -> Vec<u32> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+fn cmp_digits(a: &[u32], b: &[u32]) -> Ordering {
+    // Both are trimmed so the longer one is the larger.
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let total = u64::from(*a.get(i).unwrap_or(&0)) + u64::from(*b.get(i).unwrap_or(&0)) +
+                    carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    trim(sum)
+}
+
+// Expects a >= b.
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, digit) in a.iter().enumerate() {
+        let mut total = i64::from(*digit) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    trim(difference)
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let total = u64::from(*x) * u64::from(*y) + u64::from(product[i + j]) + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trim(product)
+}
+
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_digits(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        // A single digit divisor goes digit by digit.
+        let divisor = u64::from(b[0]);
+        let mut quotient = vec![0u32; a.len()];
+        let mut remainder = 0u64;
+        for i in (0..a.len()).rev() {
+            let current = (remainder << 32) | u64::from(a[i]);
+            quotient[i] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        return (trim(quotient), trim(vec![remainder as u32]));
+    }
+    // Otherwise shift the dividend in bit by bit, taking the divisor away whenever it fits.
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = Vec::with_capacity(b.len() + 1);
+    for bit in (0..a.len() * 32).rev() {
+        remainder = shl_digits(&remainder, 1);
+        if (a[bit / 32] >> (bit % 32)) & 1 == 1 {
+            if remainder.is_empty() {
+                remainder.push(1);
+            } else {
+                remainder[0] |= 1;
+            }
+        }
+        if cmp_digits(&remainder, b) != Ordering::Less {
+            remainder = sub_digits(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (trim(quotient), remainder)
+}
+
+// Expects bits < 32.
+fn shl_digits(a: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return a.to_vec();
+    }
+    let mut shifted = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for digit in a {
+        shifted.push((digit << bits) | carry);
+        carry = digit >> (32 - bits);
+    }
+    shifted.push(carry);
+    trim(shifted)
+}
+
+fn shr_digits(a: &[u32], bits: usize) -> Vec<u32> {
+    let (whole, part) = (bits / 32, (bits % 32) as u32);
+    let mut shifted = a.iter().skip(whole).cloned().collect::<Vec<u32>>();
+    if part > 0 {
+        for i in 0..shifted.len() {
+            let next = shifted.get(i + 1).cloned().unwrap_or(0);
+            shifted[i] = (shifted[i] >> part) | (next << (32 - part));
+        }
+    }
+    trim(shifted)
+}
+
+fn trailing_zeros(a: &[u32]) -> usize {
+    let whole = a.iter().take_while(|digit| **digit == 0).count();
+    whole * 32 + a.get(whole).map_or(0, |digit| digit.trailing_zeros() as usize)
+}
+
+fn gcd_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    // Binary GCD: only shifts and subtractions, which are cheap on digits.
+    if a.is_empty() {
+        return b.to_vec();
+    }
+    if b.is_empty() {
+        return a.to_vec();
+    }
+    let shift = trailing_zeros(a).min(trailing_zeros(b));
+    let mut a = shr_digits(a, trailing_zeros(a));
+    let mut b = b.to_vec();
+    while !b.is_empty() {
+        b = shr_digits(&b, trailing_zeros(&b));
+        if cmp_digits(&a, &b) == Ordering::Greater {
+            ::std::mem::swap(&mut a, &mut b);
+        }
+        b = sub_digits(&b, &a);
+    }
+    let mut result = a;
+    for _ in 0..shift / 31 {
+        result = shl_digits(&result, 31);
+    }
+    shl_digits(&result, (shift % 31) as u32)
+}
@@ -6,14 +6,18 @@ use math::variables::{AbstVar, new_const};
 use math::relationships::Relationship;
 
 #[derive(Clone)]
-pub struct Expression {
-    left_hand_side: Vec<AbstVar>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Expression<N> {
+    left_hand_side: Vec<AbstVar<N>>,
     relationship: Relationship,
-    right_hand_side: Vec<AbstVar>,
+    right_hand_side: Vec<AbstVar<N>>,
 }
 
-impl Expression {
-    pub fn new(l_h_s: Vec<AbstVar>, r: Relationship, r_h_s: Vec<AbstVar>) -> Expression {
+impl<N: Num> Expression<N> {
+    pub fn new(l_h_s: Vec<AbstVar<N>>,
+               r: Relationship,
+               r_h_s: Vec<AbstVar<N>>)
+               -> Expression<N> {
         Expression {
             left_hand_side: l_h_s,
             relationship: r,
@@ -21,7 +25,7 @@ impl Expression {
         }
     }
 
-    pub fn lhs(&self) -> &Vec<AbstVar> {
+    pub fn lhs(&self) -> &Vec<AbstVar<N>> {
         &self.left_hand_side
     }
 
@@ -29,7 +33,7 @@ impl Expression {
         &self.relationship
     }
 
-    pub fn rhs(&self) -> &Vec<AbstVar> {
+    pub fn rhs(&self) -> &Vec<AbstVar<N>> {
         &self.right_hand_side
     }
 
@@ -37,11 +41,11 @@ impl Expression {
         self.relationship = new_rel;
     }
 
-    pub fn add_lhs(&mut self, to_add: AbstVar) {
+    pub fn add_lhs(&mut self, to_add: AbstVar<N>) {
         insert_side(&mut self.left_hand_side, to_add, false);
     }
 
-    pub fn add_rhs(&mut self, to_add: AbstVar) {
+    pub fn add_rhs(&mut self, to_add: AbstVar<N>) {
         insert_side(&mut self.right_hand_side, to_add, false);
     }
 
@@ -49,17 +53,17 @@ impl Expression {
         let mut to_move = self.left_hand_side.remove(index);
         to_move.change_sign();
         if self.left_hand_side.is_empty() {
-            self.left_hand_side.push(new_const("RHS", 0.0));
+            self.left_hand_side.push(new_const("RHS", N::zero()));
         }
         insert_side(&mut self.right_hand_side, to_move, insert_at_start);
     }
 
-    pub fn mul_both_sides(&mut self, by: Num) {
-        mul_side(&mut self.left_hand_side, by);
-        mul_side(&mut self.right_hand_side, by);
+    pub fn mul_both_sides(&mut self, by: N) {
+        mul_side(&mut self.left_hand_side, &by);
+        mul_side(&mut self.right_hand_side, &by);
 
         // Change sign if required
-        if by.is_sign_negative() && self.relationship != Relationship::EQ {
+        if by < N::zero() && self.relationship != Relationship::EQ {
             if self.relationship == Relationship::GEQ {
                 self.relationship = Relationship::LEQ;
             } else {
@@ -78,7 +82,7 @@ impl Expression {
     }
 }
 
-impl fmt::Debug for Expression {
+impl<N: fmt::Debug> fmt::Debug for Expression<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Expression")
             .field("lhs", &self.left_hand_side)
@@ -88,7 +92,9 @@ impl fmt::Debug for Expression {
     }
 }
 
-fn collect_into_existing(side: &mut Vec<AbstVar>, to_add: AbstVar) -> Option<AbstVar> {
+fn collect_into_existing<N: Num>(side: &mut Vec<AbstVar<N>>,
+                                 to_add: AbstVar<N>)
+                                 -> Option<AbstVar<N>> {
     for mut var in side.iter_mut() {
         if var == &to_add {
             let old_var_data = var.get_data();
@@ -99,7 +105,7 @@ fn collect_into_existing(side: &mut Vec<AbstVar>, to_add: AbstVar) -> Option<Abs
     Some(to_add)
 }
 
-fn insert_side(side: &mut Vec<AbstVar>, var: AbstVar, start: bool) {
+fn insert_side<N: Num>(side: &mut Vec<AbstVar<N>>, var: AbstVar<N>, start: bool) {
     // Maybe variable already exits...
     if let Some(to_insert) = collect_into_existing(side, var) {
         // ... or not.
@@ -122,11 +128,14 @@ fn insert_side(side: &mut Vec<AbstVar>, var: AbstVar, start: bool) {
     }
 }
 
-fn mul_side(side: &mut Vec<AbstVar>, by: Num) {
+fn mul_side<N: Num>(side: &mut Vec<AbstVar<N>>, by: &N) {
     for var in side.iter_mut() {
         match var {
-            &mut AbstVar::Variable { ref mut coefficient, .. } => *coefficient = *coefficient * by,
-            &mut AbstVar::Constant { ref mut value, .. } => *value = *value * by,
+            &mut AbstVar::Variable { ref mut coefficient, .. } |
+            &mut AbstVar::ErrorVar { ref mut coefficient, .. } => {
+                *coefficient = coefficient.clone() * by.clone()
+            }
+            &mut AbstVar::Constant { ref mut value, .. } => *value = value.clone() * by.clone(),
             _ => panic!("Unexpected variant in this program logic."),
         };
     }
@@ -0,0 +1,259 @@
+use std::ops::{Add, Sub, Mul, Neg};
+use Num;
+use math::numbers::Rational;
+use math::variables::{Variable, new_const};
+use math::relationships::Relationship;
+use math::expressions::Expression;
+use objective::constraints::{Constraint, new_reg_con};
+
+/// A sum of variables with coefficients plus a constant, built up with the usual operators:
+/// `0.5 * x1 + 2.0 * x2 - 4.0`. A variable needs a coefficient, even if only `1.0 * x`, before
+/// it can be added to another one, as that is what settles the type of number.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LinearExpr<N> {
+    terms: Vec<(Variable, N)>,
+    constant: N,
+}
+
+impl<N: Num> LinearExpr<N> {
+    pub fn new() -> LinearExpr<N> {
+        LinearExpr {
+            terms: Vec::new(),
+            constant: N::zero(),
+        }
+    }
+
+    /// Every variable once, in the order it first appeared.
+    pub fn terms(&self) -> &Vec<(Variable, N)> {
+        &self.terms
+    }
+
+    pub fn constant(&self) -> &N {
+        &self.constant
+    }
+
+    pub fn add_term(&mut self, var: Variable, coefficient: N) {
+        match self.terms.iter_mut().find(|term| term.0 == var) {
+            Some(term) => term.1 += coefficient,
+            None => self.terms.push((var, coefficient)),
+        }
+    }
+
+    // Named apart from `le`, `ge` and `eq` so they do not hide the comparison operators.
+    pub fn at_most<R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        new_reg_con(self.into_expression(Relationship::LEQ, rhs.into()))
+    }
+
+    pub fn at_least<R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        new_reg_con(self.into_expression(Relationship::GEQ, rhs.into()))
+    }
+
+    pub fn equals<R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        new_reg_con(self.into_expression(Relationship::EQ, rhs.into()))
+    }
+
+    /// The relationship in the form constraints take: the variables on the left and the
+    /// constant alone on the right.
+    pub fn into_expression(self, rel: Relationship, rhs: LinearExpr<N>) -> Expression<N> {
+        let difference = self - rhs;
+        let lhs = difference.terms
+            .iter()
+            .map(|&(var, ref coefficient)| var.term(coefficient.clone()))
+            .collect();
+        Expression::new(lhs, rel, vec![new_const("RHS", -difference.constant)])
+    }
+}
+
+impl<N: Num> Default for LinearExpr<N> {
+    fn default() -> LinearExpr<N> {
+        LinearExpr::new()
+    }
+}
+
+impl<N: Num> From<N> for LinearExpr<N> {
+    fn from(constant: N) -> LinearExpr<N> {
+        LinearExpr {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl<N: Num> From<Variable> for LinearExpr<N> {
+    fn from(var: Variable) -> LinearExpr<N> {
+        LinearExpr {
+            terms: vec![(var, N::one())],
+            constant: N::zero(),
+        }
+    }
+}
+
+impl Variable {
+    pub fn at_most<N: Num, R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        LinearExpr::from(self).at_most(rhs)
+    }
+
+    pub fn at_least<N: Num, R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        LinearExpr::from(self).at_least(rhs)
+    }
+
+    pub fn equals<N: Num, R: Into<LinearExpr<N>>>(self, rhs: R) -> Constraint<N> {
+        LinearExpr::from(self).equals(rhs)
+    }
+}
+
+impl<N: Num> Add for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn add(mut self, other: LinearExpr<N>) -> LinearExpr<N> {
+        for (var, coefficient) in other.terms {
+            self.add_term(var, coefficient);
+        }
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl<N: Num> Sub for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn sub(self, other: LinearExpr<N>) -> LinearExpr<N> {
+        self + -other
+    }
+}
+
+impl<N: Num> Neg for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn neg(self) -> LinearExpr<N> {
+        self * -N::one()
+    }
+}
+
+impl<N: Num> Mul<N> for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn mul(self, by: N) -> LinearExpr<N> {
+        LinearExpr {
+            terms: self.terms
+                .into_iter()
+                .map(|(var, coefficient)| (var, coefficient * by.clone()))
+                .collect(),
+            constant: self.constant * by,
+        }
+    }
+}
+
+impl<N: Num> Add<N> for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn add(self, constant: N) -> LinearExpr<N> {
+        self + LinearExpr::from(constant)
+    }
+}
+
+impl<N: Num> Sub<N> for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn sub(self, constant: N) -> LinearExpr<N> {
+        self - LinearExpr::from(constant)
+    }
+}
+
+impl<N: Num> Add<Variable> for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn add(self, var: Variable) -> LinearExpr<N> {
+        self + LinearExpr::from(var)
+    }
+}
+
+impl<N: Num> Sub<Variable> for LinearExpr<N> {
+    type Output = LinearExpr<N>;
+
+    fn sub(self, var: Variable) -> LinearExpr<N> {
+        self - LinearExpr::from(var)
+    }
+}
+
+impl<N: Num> Add<LinearExpr<N>> for Variable {
+    type Output = LinearExpr<N>;
+
+    fn add(self, other: LinearExpr<N>) -> LinearExpr<N> {
+        LinearExpr::from(self) + other
+    }
+}
+
+impl<N: Num> Sub<LinearExpr<N>> for Variable {
+    type Output = LinearExpr<N>;
+
+    fn sub(self, other: LinearExpr<N>) -> LinearExpr<N> {
+        LinearExpr::from(self) - other
+    }
+}
+
+impl<N: Num> Add<N> for Variable {
+    type Output = LinearExpr<N>;
+
+    fn add(self, constant: N) -> LinearExpr<N> {
+        LinearExpr::from(self) + constant
+    }
+}
+
+impl<N: Num> Sub<N> for Variable {
+    type Output = LinearExpr<N>;
+
+    fn sub(self, constant: N) -> LinearExpr<N> {
+        LinearExpr::from(self) - constant
+    }
+}
+
+impl<N: Num> Mul<N> for Variable {
+    type Output = LinearExpr<N>;
+
+    fn mul(self, coefficient: N) -> LinearExpr<N> {
+        LinearExpr::from(self) * coefficient
+    }
+}
+
+// With the number on the left the impls have to be for each type of number in turn. Leaving
+// out f32 keeps float literals such as `2.0 * x` unambiguous; f32 models write `x * 2.0`.
+macro_rules! impl_scalar_ops {
+    ($($n:ty),*) => {
+        $(
+            impl Mul<Variable> for $n {
+                type Output = LinearExpr<$n>;
+
+                fn mul(self, var: Variable) -> LinearExpr<$n> {
+                    var * self
+                }
+            }
+
+            impl Mul<LinearExpr<$n>> for $n {
+                type Output = LinearExpr<$n>;
+
+                fn mul(self, exp: LinearExpr<$n>) -> LinearExpr<$n> {
+                    exp * self
+                }
+            }
+
+            impl Add<LinearExpr<$n>> for $n {
+                type Output = LinearExpr<$n>;
+
+                fn add(self, exp: LinearExpr<$n>) -> LinearExpr<$n> {
+                    exp + self
+                }
+            }
+
+            impl Sub<LinearExpr<$n>> for $n {
+                type Output = LinearExpr<$n>;
+
+                fn sub(self, exp: LinearExpr<$n>) -> LinearExpr<$n> {
+                    -exp + self
+                }
+            }
+        )*
+    }
+}
+
+impl_scalar_ops!(f64, Rational);
@@ -1,18 +1,25 @@
 pub mod variables;
 pub mod relationships;
 pub mod expressions;
+pub mod linear_exprs;
+pub mod bigints;
+pub mod numbers;
 
 #[cfg(test)]
 mod tests {
-    use math::variables::{AbstVar, new_var, new_const, new_slack_var, new_surplus_var, new_arti_var};
+    use math::variables::{AbstVar, Name, new_var, new_const, new_slack_var, new_surplus_var,
+                          new_arti_var, new_error_var, Variable, Variables, VarKey};
     use math::relationships::Relationship;
     use math::expressions::Expression;
+    use math::linear_exprs::LinearExpr;
+    use math::numbers::{Num, Rational};
+    use math::bigints::BigInt;
 
     #[test]
     fn can_create_variables() {
         let v1 = new_var("x", 2.0);
         assert_eq!(AbstVar::Variable {
-                       name: "x".to_string(),
+                       name: Name::from("x"),
                        coefficient: 2.0,
                    },
                    v1);
@@ -21,7 +28,7 @@ mod tests {
 
         let c1 = new_const("barrels in stock", 450.0);
         assert_eq!(AbstVar::Constant {
-                       name: "barrels in stock".to_string(),
+                       name: Name::from("barrels in stock"),
                        value: 450.0,
                    },
                    c1);
@@ -29,19 +36,90 @@ mod tests {
         assert_eq!(450.0, c1.get_data());
 
         let sl_var = new_slack_var("sl1".to_string());
-        assert_eq!(AbstVar::SlackVar { name: "sl1".to_string() }, sl_var);
+        assert_eq!(AbstVar::SlackVar { name: Name::from("sl1") }, sl_var);
         assert_eq!("sl1", sl_var.name());
         assert_eq!(1.0, sl_var.get_data());
 
         let su_var = new_surplus_var("su1".to_string());
-        assert_eq!(AbstVar::SurplusVar { name: "su1".to_string() }, su_var);
+        assert_eq!(AbstVar::SurplusVar { name: Name::from("su1") }, su_var);
         assert_eq!("su1", su_var.name());
         assert_eq!(-1.0, su_var.get_data());
 
         let arti_var = new_arti_var("arti1".to_string());
-        assert_eq!(AbstVar::ArtiVar { name: "arti1".to_string() }, arti_var);
+        assert_eq!(AbstVar::ArtiVar { name: Name::from("arti1") }, arti_var);
         assert_eq!("arti1", arti_var.name());
         assert_eq!(1.0, arti_var.get_data());
+
+        let error_var = new_error_var("ep1".to_string(), -1.0);
+        assert_eq!(AbstVar::ErrorVar {
+                       name: Name::from("ep1"),
+                       coefficient: -1.0,
+                   },
+                   error_var);
+        assert_eq!("ep1", error_var.name());
+        assert_eq!(-1.0, error_var.get_data());
+    }
+
+    #[test]
+    fn can_create_variable_handles() {
+        let mut variables = Variables::new();
+        let x = variables.add("x");
+        let y = variables.add_unnamed();
+        assert_eq!(x, variables.add("x"));
+        assert!(x != y);
+        assert_eq!(Some(x), variables.get("x"));
+        assert_eq!(None, variables.get("z"));
+        assert_eq!(Some("x"), variables.name(x));
+        assert_eq!(None, variables.name(y));
+        assert_eq!(2, variables.len());
+        assert_eq!(vec![x, y], variables.iter().collect::<Vec<Variable>>());
+
+        let term = x.term(2.0);
+        assert_eq!(Name::Handle(x), *term.name());
+        assert!(y.var_key() != *term.name());
+        assert!("x".var_key() != *term.name());
+        assert!("v#0".var_key() != *term.name());
+        assert_eq!(Some(x), term.name().handle());
+        assert_eq!(None, Name::from("x").handle());
+        assert_eq!("x", variables.display_name(term.name()));
+        assert_eq!("v#1", variables.display_name(&y.var_key()));
+        assert_eq!("sl1", variables.display_name(&Name::from("sl1")));
+
+        // Handles from another registry never stand for these variables.
+        let mut others = Variables::new();
+        let z = others.add("x");
+        assert_eq!(x.id(), z.id());
+        assert!(x != z);
+        assert!(z.var_key() != *term.name());
+        assert!(!variables.owns(z));
+        assert_eq!(None, variables.name(z));
+        assert_eq!("v#0", variables.display_name(&z.var_key()));
+    }
+
+    #[test]
+    fn can_build_linear_exprs() {
+        let mut variables = Variables::new();
+        let x = variables.add("x");
+        let y = variables.add("y");
+        let exp = 0.5 * x + 2.0 * y - 1.0 * x + 3.0;
+        assert_eq!(&vec![(x, -0.5), (y, 2.0)], exp.terms());
+        assert_eq!(3.0, *exp.constant());
+        let exp = -(exp * 2.0) + y;
+        assert_eq!(&vec![(x, 1.0), (y, -3.0)], exp.terms());
+        assert_eq!(-6.0, *exp.constant());
+        assert_eq!(LinearExpr::from(x) * Rational::new(1, 2),
+                   Rational::new(1, 2) * LinearExpr::from(x));
+        // Building constraints leaves the comparisons of handles and expressions alone.
+        assert!(x.eq(&variables.add("x")));
+        assert!(!x.eq(&y));
+        assert!(x.le(&y));
+        assert!(!(1.0 * x).eq(&(1.0 * y)));
+
+        // Everything moves to the left but the constant.
+        let exp = (2.0 * x + 1.0).into_expression(Relationship::LEQ, 1.0 * y + 5.0);
+        assert_eq!(&vec![x.term(2.0), y.term(-1.0)], exp.lhs());
+        assert_eq!(&Relationship::LEQ, exp.rel());
+        assert_eq!(&vec![new_const("RHS", 4.0)], exp.rhs());
     }
 
     #[test]
@@ -57,6 +135,68 @@ mod tests {
         assert_eq!("x", v2.name());
     }
 
+    #[test]
+    fn can_do_big_int_arithmetic() {
+        let two_pow_64 = BigInt::from(1 << 32) * BigInt::from(1 << 32);
+        assert_eq!("18446744073709551616", two_pow_64.to_string());
+        let square = &two_pow_64 * &two_pow_64;
+        assert_eq!("340282366920938463463374607431768211456", square.to_string());
+        assert_eq!(two_pow_64, &square / &two_pow_64);
+        assert!((&square % &two_pow_64).is_zero());
+        let odd = &square + &BigInt::from(7);
+        assert_eq!(BigInt::from(7), &odd % &two_pow_64);
+        assert_eq!(BigInt::from(-7), -odd.clone() % two_pow_64.clone());
+        assert_eq!(square, &odd - &BigInt::from(7));
+        assert_eq!(BigInt::from(-5), BigInt::from(3) - BigInt::from(8));
+        assert_eq!(BigInt::from(-2), BigInt::from(-7) / BigInt::from(3));
+        assert_eq!("-1000000000", BigInt::from(-1_000_000_000).to_string());
+        assert!(BigInt::from(-3) < BigInt::from(2));
+        assert!(square > two_pow_64);
+        assert!(-square.clone() < -two_pow_64.clone());
+    }
+
+    #[test]
+    fn can_get_big_int_gcd() {
+        assert_eq!(BigInt::from(6), BigInt::from(-12).gcd(&BigInt::from(18)));
+        assert_eq!(BigInt::from(5), BigInt::zero().gcd(&BigInt::from(-5)));
+        let two_pow_64 = BigInt::from(1 << 32) * BigInt::from(1 << 32);
+        let multiple = &two_pow_64 * &BigInt::from(9);
+        assert_eq!(&two_pow_64 * &BigInt::from(3),
+                   (&two_pow_64 * &BigInt::from(6)).gcd(&multiple));
+    }
+
+    #[test]
+    fn can_do_rational_arithmetic() {
+        let half = Rational::new(2, 4);
+        assert_eq!(&BigInt::from(1), half.numer());
+        assert_eq!(&BigInt::from(2), half.denom());
+        assert_eq!(Rational::new(1, -3), Rational::new(-1, 3));
+        assert_eq!(Rational::new(5, 6), half.clone() + Rational::new(1, 3));
+        assert_eq!(Rational::new(1, 6), half.clone() - Rational::new(1, 3));
+        assert_eq!(Rational::new(1, 6), half.clone() * Rational::new(1, 3));
+        assert_eq!(Rational::new(3, 2), half.clone() / Rational::new(1, 3));
+        assert_eq!(Rational::new(-1, 2), -half.clone());
+        assert!(Rational::new(1, 3) < half);
+        assert!(-half.clone() < Rational::zero());
+        assert_eq!(Rational::one(), Rational::from_i32(3) * Rational::new(1, 3));
+        assert_eq!("1/2", half.to_string());
+        assert_eq!("-4", Rational::from_i32(-4).to_string());
+    }
+
+    #[test]
+    fn can_keep_rationals_exact_beyond_machine_integers() {
+        // Squaring twice over overflows 128 bits without any common factor to cancel.
+        let mut x = Rational::new(i64::MAX, i64::MAX - 1);
+        for _ in 0..2 {
+            x = x.clone() * x;
+        }
+        let expected = BigInt::from(i64::MAX) * BigInt::from(i64::MAX);
+        assert_eq!(&(&expected * &expected), x.numer());
+        let cube = x / Rational::new(i64::MAX, i64::MAX - 1);
+        let base = BigInt::from(i64::MAX - 1);
+        assert_eq!(&(&(&base * &base) * &base), cube.denom());
+    }
+
     #[test]
     fn can_create_relationships() {
         let r1: Relationship = Relationship::EQ;
@@ -69,7 +209,7 @@ mod tests {
 
     #[test]
     fn can_create_expressions() {
-        let e: Expression =
+        let e: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::EQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -86,7 +226,7 @@ mod tests {
 
     #[test]
     fn can_set_rels() {
-        let mut exp: Expression =
+        let mut exp: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::LEQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -96,7 +236,7 @@ mod tests {
 
     #[test]
     fn can_add_to_sides() {
-        let mut exp1: Expression =
+        let mut exp1: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::EQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -119,7 +259,7 @@ mod tests {
         assert_eq!("bonus", exp1.rhs()[2].name());
         assert_eq!(1000.0, exp1.rhs()[2].get_data());
 
-        let mut exp2: Expression =
+        let mut exp2: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::EQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -145,7 +285,7 @@ mod tests {
 
     #[test]
     fn can_move_from_lhs_side() {
-        let mut e1: Expression =
+        let mut e1: Expression<f32> =
             Expression::new(vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)],
                             Relationship::GEQ,
                             vec![new_var("Z", 1.0)]);
@@ -191,7 +331,7 @@ mod tests {
         assert_eq!(-1000.0, e1.rhs()[3].get_data());
 
 
-        let mut e2: Expression =
+        let mut e2: Expression<f32> =
             Expression::new(vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)],
                             Relationship::GEQ,
                             vec![new_var("Z", 1.0), new_slack_var("s1".to_string())]);
@@ -245,7 +385,7 @@ mod tests {
 
     #[test]
     fn can_mul_both_sides_of_expressions() {
-        let mut exp: Expression =
+        let mut exp: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::GEQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -263,7 +403,7 @@ mod tests {
 
     #[test]
     fn can_swap_sides() {
-        let mut exp: Expression =
+        let mut exp: Expression<f32> =
             Expression::new(vec![new_var("Z", 1.0)],
                             Relationship::EQ,
                             vec![new_var("x", 2.0), new_var("y", 3.0), new_const("bonus", 1000.0)]);
@@ -286,4 +426,27 @@ mod tests {
         exp.set_rel(Relationship::GEQ);
         assert!(exp.swap_sides().is_err());
     }
+
+    #[test]
+    fn can_convert_decimals() {
+        assert_eq!(Some(2.5), f64::from_decimal("2.5"));
+        assert_eq!(Some(1200.0), f64::from_decimal("1.2e3"));
+        assert_eq!(None, f64::from_decimal("inf"));
+        assert_eq!(None, f64::from_decimal("1e999"));
+        assert_eq!(None, f32::from_decimal("1e39"));
+        assert_eq!("2.5", 2.5f64.to_decimal());
+
+        assert_eq!(Some(Rational::new(5, 2)), Rational::from_decimal("2.5"));
+        assert_eq!(Some(Rational::new(-3, 4)), Rational::from_decimal("-75e-2"));
+        assert_eq!(Some(Rational::new(1, 3)), Rational::from_decimal("1/3"));
+        assert_eq!(None, Rational::from_decimal("1/0"));
+        assert_eq!(None, Rational::from_decimal("x"));
+        assert_eq!(None, Rational::from_decimal("1e999999999"));
+        assert_eq!(None, Rational::from_decimal("1.5e-2147483648"));
+        assert_eq!(None, Rational::from_decimal("1e4001"));
+        assert_eq!(Some(Rational::new(1, 1)), Rational::from_decimal("0.001e3"));
+        assert_eq!("-0.75", Rational::new(-3, 4).to_decimal());
+        assert_eq!("12", Rational::new(12, 1).to_decimal());
+        assert_eq!("0.66666666666666666667", Rational::new(2, 3).to_decimal());
+    }
 }
@@ -0,0 +1,319 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign};
+use math::bigints::BigInt;
+
+/// The arithmetic a model is solved in.
+pub trait Num
+    : Clone + PartialOrd + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Neg<Output = Self> + AddAssign + SubAssign {
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn from_i32(n: i32) -> Self;
+
+    /// How far a computed value may stray from the exact one by default and still count as it.
+    fn tolerance() -> Self;
+
+    /// Reads a number written in decimal, with an optional sign, fraction and exponent, as in
+    /// "-12", "0.5" or "1.5e-3".
+    fn from_decimal(text: &str) -> Option<Self>;
+
+    /// Writes the number in decimal so `from_decimal` can read it back.
+    fn to_decimal(&self) -> String;
+}
+
+macro_rules! impl_num_for_float {
+    ($float:ty, $tolerance:expr) => {
+        impl Num for $float {
+            fn zero() -> $float {
+                0.0
+            }
+
+            fn one() -> $float {
+                1.0
+            }
+
+            fn from_i32(n: i32) -> $float {
+                n as $float
+            }
+
+            fn tolerance() -> $float {
+                $tolerance
+            }
+
+            fn from_decimal(text: &str) -> Option<$float> {
+                // Leave out the names of infinity and NaN, which parse would take too.
+                let is_decimal = text.chars().all(|c| {
+                    c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || c == '+' || c == '-'
+                });
+                if is_decimal {
+                    // Too large an exponent parses as infinity.
+                    text.parse::<$float>().ok().filter(|value| value.is_finite())
+                } else {
+                    None
+                }
+            }
+
+            fn to_decimal(&self) -> String {
+                self.to_string()
+            }
+        }
+    }
+}
+
+impl_num_for_float!(f32, 1e-5);
+impl_num_for_float!(f64, 1e-9);
+
+/// An exact fraction kept in lowest terms with a positive denominator. Numerator and
+/// denominator grow as needed so no operation ever rounds.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl Rational {
+    pub fn new(numer: i64, denom: i64) -> Rational {
+        Rational::from_big(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    pub fn from_big(numer: BigInt, denom: BigInt) -> Rational {
+        if denom.is_zero() {
+            panic!("Rational with a zero denominator.");
+        }
+        let mut divisor = numer.gcd(&denom);
+        if divisor.is_zero() {
+            divisor = BigInt::from(1);
+        }
+        if denom.is_negative() {
+            divisor = -divisor;
+        }
+        Rational {
+            numer: &numer / &divisor,
+            denom: &denom / &divisor,
+        }
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+}
+
+impl Num for Rational {
+    fn zero() -> Rational {
+        Rational::new(0, 1)
+    }
+
+    fn one() -> Rational {
+        Rational::new(1, 1)
+    }
+
+    fn from_i32(n: i32) -> Rational {
+        Rational::new(i64::from(n), 1)
+    }
+
+    fn tolerance() -> Rational {
+        // Exact arithmetic never rounds, so nothing short of equal is equal.
+        Rational::zero()
+    }
+
+    fn from_decimal(text: &str) -> Option<Rational> {
+        // Fractions as Rational writes them read back too.
+        if let Some((numer, denom)) = text.split_once('/') {
+            let numer = Rational::from_decimal(numer)?;
+            let denom = Rational::from_decimal(denom)?;
+            return if denom.numer.is_zero() {
+                None
+            } else {
+                Some(numer / denom)
+            };
+        }
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(index) => (&text[..index], text[index + 1..].parse::<i32>().ok()?),
+            None => (text, 0),
+        };
+        let (negative, mantissa) = match mantissa.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        let mut digits = BigInt::zero();
+        for c in whole.chars().chain(fraction.chars()) {
+            let digit = c.to_digit(10)?;
+            digits = &(&digits * &BigInt::from(10)) + &BigInt::from(i64::from(digit));
+        }
+        if negative {
+            digits = -digits;
+        }
+        // The digits are the number times ten to the power of the digits after the point.
+        let scale = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+        if scale.unsigned_abs() > MAX_DECIMAL_SCALE {
+            return None;
+        }
+        let power = power_of_ten(scale.unsigned_abs());
+        Some(if scale < 0 {
+            Rational::from_big(digits, power)
+        } else {
+            Rational::from_big(&digits * &power, BigInt::from(1))
+        })
+    }
+
+    fn to_decimal(&self) -> String {
+        // A fraction ends in decimal if its denominator has no prime factors but 2 and 5, after
+        // as many digits as the larger of their powers. Any other gets rounded.
+        let mut rest = self.denom.clone();
+        let mut twos = 0;
+        let mut fives = 0;
+        while (&rest % &BigInt::from(2)).is_zero() {
+            rest = &rest / &BigInt::from(2);
+            twos += 1;
+        }
+        while (&rest % &BigInt::from(5)).is_zero() {
+            rest = &rest / &BigInt::from(5);
+            fives += 1;
+        }
+        let places = if rest == BigInt::from(1) {
+            twos.max(fives)
+        } else {
+            ROUNDED_DECIMAL_PLACES
+        };
+        // Round half away from zero at the last place.
+        let scaled = &self.numer.abs() * &power_of_ten(places);
+        let two = BigInt::from(2);
+        let rounded = &(&(&scaled * &two) + &self.denom) / &(&self.denom * &two);
+        let mut digits = rounded.to_string();
+        let places = places as usize;
+        if digits.len() <= places {
+            digits = format!("{}{}", "0".repeat(places + 1 - digits.len()), digits);
+        }
+        let (whole, fraction) = digits.split_at(digits.len() - places);
+        let fraction = fraction.trim_end_matches('0');
+        let sign = if self.numer.is_negative() && !rounded.is_zero() { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{}{}", sign, whole)
+        } else {
+            format!("{}{}.{}", sign, whole, fraction)
+        }
+    }
+}
+
+// Digits after the point a fraction is rounded to when it does not end in decimal.
+const ROUNDED_DECIMAL_PLACES: u32 = 20;
+
+// The largest power of ten a decimal may be scaled by, far beyond any float, so that reading one
+// does not take ages building a huge number.
+const MAX_DECIMAL_SCALE: u32 = 4000;
+
+fn power_of_ten(exponent: u32) -> BigInt {
+    (0..exponent).fold(BigInt::from(1), |power, _| &power * &BigInt::from(10))
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        Rational::from_big(&(&self.numer * &other.denom) + &(&other.numer * &self.denom),
+                           &self.denom * &other.denom)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, other: Rational) {
+        *self = self.clone() + other;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, other: Rational) {
+        *self = self.clone() - other;
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::from_big(&self.numer * &other.numer, &self.denom * &other.denom)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        if other.numer.is_zero() {
+            panic!("Rational division by zero.");
+        }
+        Rational::from_big(&self.numer * &other.denom, &self.denom * &other.numer)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // Both denominators are positive so cross multiplying keeps the order.
+        (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == BigInt::from(1) {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+// Written out as the fraction it is, so nothing is lost on the way through.
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Rational {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Rational {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Rational, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Rational::from_decimal(&text).ok_or_else(|| {
+            ::serde::de::Error::custom(format!("\"{}\" is not a rational number.", text))
+        })
+    }
+}
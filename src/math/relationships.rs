@@ -1,4 +1,5 @@
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Relationship {
     EQ,
     LEQ,
@@ -1,24 +1,285 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use Num;
 
+// Handles are displayed by this prefix and their id, which named variables do not start with
+// so the two always read apart.
+const HANDLE_PREFIX: &str = "v#";
+
+// Every registry of variables hands out handles of its own.
+static NEXT_OWNER: AtomicUsize = AtomicUsize::new(0);
+
+/// A handle on a decision variable, cheap to copy, compare and hash. Only the `Variables` that
+/// handed it out know its name, so a misspelt name can not turn into a new variable unnoticed,
+/// and handles from different registries never stand for the same variable.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Variable {
+    owner: usize,
+    id: usize,
+}
+
+impl Variable {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Whether both handles came from the same registry.
+    pub fn shares_owner(&self, other: &Variable) -> bool {
+        self.owner == other.owner
+    }
+
+    /// This variable with a coefficient, ready to go into an expression.
+    pub fn term<N>(&self, coefficient: N) -> AbstVar<N> {
+        AbstVar::Variable {
+            name: Name::Handle(*self),
+            coefficient,
+        }
+    }
+}
+
+/// What a variable goes by in expressions, tables and solutions: the name it was made with, or
+/// the handle of a variable made from one.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum Name {
+    Text(String),
+    Handle(Variable),
+}
+
+impl Name {
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Name::Text(ref text) => Some(text),
+            Name::Handle(_) => None,
+        }
+    }
+
+    pub fn handle(&self) -> Option<Variable> {
+        match *self {
+            Name::Text(_) => None,
+            Name::Handle(var) => Some(var),
+        }
+    }
+}
+
+// Names show up in the debug output of whole models, where a plain string reads best.
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Name::Text(ref text) => fmt::Debug::fmt(text, f),
+            Name::Handle(ref var) => f.debug_tuple("Handle").field(var).finish(),
+        }
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Name::Text(ref text) => write!(f, "{}", text),
+            Name::Handle(var) => write!(f, "{}{}", HANDLE_PREFIX, var.id),
+        }
+    }
+}
+
+impl From<&str> for Name {
+    fn from(text: &str) -> Name {
+        Name::Text(text.to_string())
+    }
+}
+
+impl From<String> for Name {
+    fn from(text: String) -> Name {
+        Name::Text(text)
+    }
+}
+
+impl From<Variable> for Name {
+    fn from(var: Variable) -> Name {
+        Name::Handle(var)
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == Some(other.as_str())
+    }
+}
+
+impl PartialEq<Name> for str {
+    fn eq(&self, other: &Name) -> bool {
+        *other == *self
+    }
+}
+
+impl PartialEq<Name> for &str {
+    fn eq(&self, other: &Name) -> bool {
+        *other == **self
+    }
+}
+
+impl PartialEq<Name> for String {
+    fn eq(&self, other: &Name) -> bool {
+        *other == *self
+    }
+}
+
+/// What a variable is looked up by in tables and solutions: its handle or, for variables made
+/// with `new_var`, its name.
+pub trait VarKey {
+    fn var_key(&self) -> Name;
+}
+
+impl VarKey for Variable {
+    fn var_key(&self) -> Name {
+        Name::Handle(*self)
+    }
+}
+
+impl VarKey for str {
+    fn var_key(&self) -> Name {
+        Name::from(self)
+    }
+}
+
+impl VarKey for String {
+    fn var_key(&self) -> Name {
+        Name::from(self.as_str())
+    }
+}
+
+impl VarKey for Name {
+    fn var_key(&self) -> Name {
+        self.clone()
+    }
+}
+
+/// Whether a name reads like a handle, which a variable of the model must not be named for it
+/// to be told apart from one in solutions and files.
+pub fn is_reserved_name(name: &str) -> bool {
+    name.starts_with(HANDLE_PREFIX)
+}
+
+/// Hands out the variables of a model. Names are optional and only used for display; asking
+/// for the same name twice gives back the same variable.
 #[derive(Debug, Clone)]
-pub enum AbstVar {
-    Variable { name: String, coefficient: Num },
-    Constant { name: String, value: Num },
-    SlackVar { name: String },
-    SurplusVar { name: String },
-    ArtiVar { name: String },
+pub struct Variables {
+    owner: usize,
+    names: Vec<Option<String>>,
+    by_name: HashMap<String, Variable>,
 }
 
-impl Hash for AbstVar {
+impl Default for Variables {
+    fn default() -> Variables {
+        Variables {
+            owner: NEXT_OWNER.fetch_add(1, Ordering::Relaxed),
+            names: Vec::new(),
+            by_name: HashMap::new(),
+        }
+    }
+}
+
+impl Variables {
+    pub fn new() -> Variables {
+        Variables::default()
+    }
+
+    pub fn add(&mut self, name: &str) -> Variable {
+        if let Some(&var) = self.by_name.get(name) {
+            return var;
+        }
+        let var = self.next_var();
+        self.names.push(Some(name.to_string()));
+        self.by_name.insert(name.to_string(), var);
+        var
+    }
+
+    pub fn add_unnamed(&mut self) -> Variable {
+        let var = self.next_var();
+        self.names.push(None);
+        var
+    }
+
+    pub fn get(&self, name: &str) -> Option<Variable> {
+        self.by_name.get(name).cloned()
+    }
+
+    /// Whether a handle came from this registry.
+    pub fn owns(&self, var: Variable) -> bool {
+        var.owner == self.owner && var.id < self.names.len()
+    }
+
+    pub fn name(&self, var: Variable) -> Option<&str> {
+        if !self.owns(var) {
+            return None;
+        }
+        self.names[var.id].as_deref()
+    }
+
+    /// The name of the variable behind a column or solution entry, or the entry as displayed
+    /// when it has none.
+    pub fn display_name(&self, name: &Name) -> String {
+        match name.handle().and_then(|var| self.name(var)) {
+            Some(text) => text.to_string(),
+            None => name.to_string(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Variable> {
+        let owner = self.owner;
+        (0..self.names.len()).map(move |id| Variable { owner, id })
+    }
+
+    fn next_var(&self) -> Variable {
+        Variable {
+            owner: self.owner,
+            id: self.names.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AbstVar<N> {
+    Variable { name: Name, coefficient: N },
+    Constant { name: Name, value: N },
+    SlackVar { name: Name },
+    SurplusVar { name: Name },
+    ArtiVar { name: Name },
+    ErrorVar { name: Name, coefficient: N },
+}
+
+impl<N> Hash for AbstVar<N> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.name().hash(state);
     }
 }
 
-impl PartialEq for AbstVar {
+impl<N> PartialEq for AbstVar<N> {
     #[cfg_attr(rustfmt, rustfmt_skip)]
-    fn eq(&self, other: &AbstVar) -> bool {
+    fn eq(&self, other: &AbstVar<N>) -> bool {
         match (self, other) {
             (&AbstVar::Variable { name: ref n1, .. }, &AbstVar::Variable { name: ref n2, .. }) =>
                 *n1 == *n2,
@@ -30,37 +291,44 @@ impl PartialEq for AbstVar {
                 *n1 == *n2,
             (&AbstVar::ArtiVar { name: ref n1 }, &AbstVar::ArtiVar { name: ref n2 }) =>
                 *n1 == *n2,
+            (&AbstVar::ErrorVar { name: ref n1, .. }, &AbstVar::ErrorVar { name: ref n2, .. }) =>
+                *n1 == *n2,
             _ => false,
         }
     }
 }
 
-impl Eq for AbstVar {}
+impl<N> Eq for AbstVar<N> {}
 
-impl AbstVar {
-    pub fn name(&self) -> &String {
+impl<N> AbstVar<N> {
+    pub fn name(&self) -> &Name {
         match self {
             &AbstVar::Variable { ref name, .. } |
-            &AbstVar::Constant { ref name, .. } => name,
+            &AbstVar::Constant { ref name, .. } |
+            &AbstVar::ErrorVar { ref name, .. } => name,
             &AbstVar::SlackVar { ref name } |
             &AbstVar::SurplusVar { ref name } |
             &AbstVar::ArtiVar { ref name } => name,
         }
     }
+}
 
-    pub fn get_data(&self) -> Num {
+impl<N: Num> AbstVar<N> {
+    pub fn get_data(&self) -> N {
         match self {
-            &AbstVar::Variable { ref coefficient, .. } => *coefficient,
-            &AbstVar::Constant { ref value, .. } => *value,
+            &AbstVar::Variable { ref coefficient, .. } |
+            &AbstVar::ErrorVar { ref coefficient, .. } => coefficient.clone(),
+            &AbstVar::Constant { ref value, .. } => value.clone(),
             &AbstVar::SlackVar { .. } |
-            &AbstVar::ArtiVar { .. } => 1.0,
-            &AbstVar::SurplusVar { .. } => -1.0,
+            &AbstVar::ArtiVar { .. } => N::one(),
+            &AbstVar::SurplusVar { .. } => -N::one(),
         }
     }
 
-    pub fn set_data(&mut self, d: Num) {
+    pub fn set_data(&mut self, d: N) {
         match self {
-            &mut AbstVar::Variable { ref mut coefficient, .. } => *coefficient = d,
+            &mut AbstVar::Variable { ref mut coefficient, .. } |
+            &mut AbstVar::ErrorVar { ref mut coefficient, .. } => *coefficient = d,
             &mut AbstVar::Constant { ref mut value, .. } => *value = d,
             _ => panic!("This variant does not support this method call."),
         };
@@ -68,50 +336,47 @@ impl AbstVar {
 
     pub fn change_sign(&mut self) {
         match self {
-            &mut AbstVar::Variable { ref mut coefficient, .. } => *coefficient *= -1.0,
-            &mut AbstVar::Constant { ref mut value, .. } => *value *= -1.0,
+            &mut AbstVar::Variable { ref mut coefficient, .. } |
+            &mut AbstVar::ErrorVar { ref mut coefficient, .. } => {
+                *coefficient = -coefficient.clone()
+            }
+            &mut AbstVar::Constant { ref mut value, .. } => *value = -value.clone(),
             _ => panic!("This variant does not support this method call."),
         }
     }
 }
 
-pub fn new_var(n: &str, c: Num) -> AbstVar {
+/// A variable known by its name. The solver turns down names that start with "v#" as handles
+/// are displayed.
+pub fn new_var<N>(n: &str, c: N) -> AbstVar<N> {
     AbstVar::Variable {
-        name: n.to_string(),
+        name: Name::from(n),
         coefficient: c,
     }
 }
 
-pub fn new_const(n: &str, v: Num) -> AbstVar {
+pub fn new_const<N>(n: &str, v: N) -> AbstVar<N> {
     AbstVar::Constant {
-        name: n.to_string(),
+        name: Name::from(n),
         value: v,
     }
 }
 
-pub fn new_slack_var(n: String) -> AbstVar {
-    AbstVar::SlackVar { name: n }
+pub fn new_slack_var<N>(n: String) -> AbstVar<N> {
+    AbstVar::SlackVar { name: Name::from(n) }
 }
 
-pub fn new_surplus_var(n: String) -> AbstVar {
-    AbstVar::SurplusVar { name: n }
+pub fn new_surplus_var<N>(n: String) -> AbstVar<N> {
+    AbstVar::SurplusVar { name: Name::from(n) }
 }
 
-pub fn new_arti_var(n: String) -> AbstVar {
-    AbstVar::ArtiVar { name: n }
+pub fn new_arti_var<N>(n: String) -> AbstVar<N> {
+    AbstVar::ArtiVar { name: Name::from(n) }
 }
 
-pub fn is_gen_arti_var(name: &String) -> bool {
-    if name.len() < 4 {
-        return false;
-    }
-    let (part1, part2) = name.split_at(4);
-    if part1 != "arti" {
-        return false;
-    } else {
-        match part2.parse::<usize>() {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+pub fn new_error_var<N>(n: String, c: N) -> AbstVar<N> {
+    AbstVar::ErrorVar {
+        name: Name::from(n),
+        coefficient: c,
     }
 }
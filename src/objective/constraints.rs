@@ -1,34 +1,56 @@
 use math::variables::AbstVar;
 use math::expressions::Expression;
+use objective::strengths::Strength;
 
 #[derive(Clone)]
-pub enum Constraint {
-    Regular(Expression),
-    NonNegative(AbstVar),
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Constraint<N> {
+    Regular(Expression<N>),
+    NonNegative(AbstVar<N>),
+    Weighted(Expression<N>, Strength<N>),
+    // Keeps a variable between a lower bound and an optional upper one without a row of its own
+    // in the table. The lower bound takes the place of zero, so it may be negative.
+    Bounded(AbstVar<N>, N, Option<N>),
+    // Weakly holds a variable at its last solved value. Only a solver that solves more than
+    // once has such a value, so `optimise` leaves these out.
+    Stay(AbstVar<N>),
 }
 
-pub struct SystemOfConstraints {
-    constraints: Vec<Constraint>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemOfConstraints<N> {
+    constraints: Vec<Constraint<N>>,
 }
 
-impl SystemOfConstraints {
-    pub fn new(c: Vec<Constraint>) -> SystemOfConstraints {
+impl<N> SystemOfConstraints<N> {
+    pub fn new(c: Vec<Constraint<N>>) -> SystemOfConstraints<N> {
         SystemOfConstraints { constraints: c }
     }
 
-    pub fn system(&self) -> &Vec<Constraint> {
+    pub fn system(&self) -> &Vec<Constraint<N>> {
         &self.constraints
     }
 
-    pub fn system_mut(&mut self) -> &mut Vec<Constraint> {
+    pub fn system_mut(&mut self) -> &mut Vec<Constraint<N>> {
         &mut self.constraints
     }
 }
 
-pub fn new_reg_con(exp: Expression) -> Constraint {
+pub fn new_reg_con<N>(exp: Expression<N>) -> Constraint<N> {
     Constraint::Regular(exp)
 }
 
-pub fn new_non_neg_con(var: AbstVar) -> Constraint {
+pub fn new_non_neg_con<N>(var: AbstVar<N>) -> Constraint<N> {
     Constraint::NonNegative(var)
 }
+
+pub fn new_weighted_con<N>(exp: Expression<N>, strength: Strength<N>) -> Constraint<N> {
+    Constraint::Weighted(exp, strength)
+}
+
+pub fn new_bounded_con<N>(var: AbstVar<N>, lower: N, upper: Option<N>) -> Constraint<N> {
+    Constraint::Bounded(var, lower, upper)
+}
+
+pub fn new_stay_con<N>(var: AbstVar<N>) -> Constraint<N> {
+    Constraint::Stay(var)
+}
@@ -1,30 +1,36 @@
-use math::variables::new_var;
+use math::variables::{Name, new_var};
 use math::relationships::Relationship;
 use math::expressions::Expression;
 use objective::problems::ProblemType;
+use Num;
 
-pub struct Function {
-    expression: Expression,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Function<N> {
+    name: Name,
+    expression: Expression<N>,
     problem_type: ProblemType,
-    expression_max: Option<Expression>,
+    expression_max: Option<Expression<N>>,
 }
 
-impl Function {
-    pub fn new(e: Expression, p_t: ProblemType) -> Function {
+impl<N: Num> Function<N> {
+    pub fn new(e: Expression<N>, p_t: ProblemType) -> Function<N> {
         // Covert minimisation problems to maximasation so we can keep the Simplex in one form
         // (Maximasation Form).
         let e_m = match p_t {
             ProblemType::MAX => None,
             ProblemType::MIN => Some(create_expression_to_max(&e)),
         };
+        // Remember the name now as rearranging the expression moves the variable around.
+        let n = e.lhs().first().map(|var| var.name().clone()).unwrap_or_else(|| Name::from(""));
         Function {
+            name: n,
             expression: e,
             problem_type: p_t,
             expression_max: e_m,
         }
     }
 
-    pub fn exp(&self) -> &Expression {
+    pub fn exp(&self) -> &Expression<N> {
         &self.expression
     }
 
@@ -32,16 +38,11 @@ impl Function {
         &self.problem_type
     }
 
-    pub fn name(&self) -> String {
-        if self.problem_type == ProblemType::MAX {
-            let last_index = self.expression.lhs().len() - 1;
-            self.expression.lhs()[last_index].name().clone()
-        } else {
-            self.expression.lhs()[0].name().clone()
-        }
+    pub fn name(&self) -> Name {
+        self.name.clone()
     }
 
-    pub fn exp_max(&self) -> &Expression {
+    pub fn exp_max(&self) -> &Expression<N> {
         if let Some(ref exp_to_max) = self.expression_max {
             &exp_to_max
         } else {
@@ -49,7 +50,7 @@ impl Function {
         }
     }
 
-    pub fn exp_max_mut(&mut self) -> &mut Expression {
+    pub fn exp_max_mut(&mut self) -> &mut Expression<N> {
         if let Some(ref mut exp_to_max) = self.expression_max {
             exp_to_max
         } else {
@@ -58,7 +59,7 @@ impl Function {
     }
 }
 
-fn create_expression_to_max(expression: &Expression) -> Expression {
+fn create_expression_to_max<N: Num>(expression: &Expression<N>) -> Expression<N> {
     let original_rhs = expression.rhs();
     let mut rhs_max = Vec::with_capacity(original_rhs.len());
     for var in original_rhs {
@@ -66,5 +67,5 @@ fn create_expression_to_max(expression: &Expression) -> Expression {
         var_clone.change_sign();
         rhs_max.push(var_clone);
     }
-    Expression::new(vec![new_var("Q", 1.0)], Relationship::EQ, rhs_max)
+    Expression::new(vec![new_var("Q", N::one())], Relationship::EQ, rhs_max)
 }
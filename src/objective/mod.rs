@@ -1,17 +1,21 @@
 pub mod problems;
 pub mod functions;
 pub mod constraints;
+pub mod strengths;
 pub mod solvers;
 
 #[cfg(test)]
 mod tests {
-    use math::variables::{AbstVar, new_var, new_const};
+    use math::variables::{AbstVar, Name, new_var, new_const, new_error_var};
     use math::relationships::Relationship;
     use math::expressions::Expression;
     use objective::problems::ProblemType;
     use objective::functions::Function;
-    use objective::constraints::{Constraint, new_reg_con, new_non_neg_con, SystemOfConstraints};
-    use objective::solvers::{transform_constraint_rels_to_eq, rearrange_fun_eq_zero};
+    use objective::constraints::{Constraint, new_reg_con, new_non_neg_con, new_bounded_con,
+                                 new_weighted_con, new_stay_con, SystemOfConstraints};
+    use objective::strengths::Strength;
+    use objective::solvers::{transform_constraint_rels_to_eq, rearrange_fun_eq_zero,
+                             add_error_vars, get_dual_markers};
 
     #[test]
     fn can_create_problem_types() {
@@ -109,6 +113,42 @@ mod tests {
             }
             _ => panic!("Unexpected variant."),
         }
+
+        let c3 = new_stay_con(new_var("y", 1.0));
+        match c3 {
+            Constraint::Stay(abst_var) => assert_eq!("y", abst_var.name()),
+            _ => panic!("Unexpected variant."),
+        }
+    }
+
+    #[test]
+    fn can_create_strengths() {
+        assert_eq!(None, Strength::<f32>::Required.weight());
+        assert_eq!(Some(1_000_000.0), Strength::Strong.weight());
+        assert_eq!(Some(1_000.0), Strength::Medium.weight());
+        assert_eq!(Some(1.0), Strength::Weak.weight());
+        assert_eq!(Some(2.5), Strength::Custom(2.5).weight());
+    }
+
+    #[test]
+    fn can_create_weighted_constraints() {
+        let exp = Expression::new(vec![new_var("x", 2.0), new_var("y", 3.0)],
+                                  Relationship::LEQ,
+                                  vec![new_const("volume", 2300.0)]);
+        let c1 = new_weighted_con(exp, Strength::Medium);
+        match c1 {
+            Constraint::Weighted(exp, strength) => {
+                assert_eq!("x", exp.lhs()[0].name());
+                assert_eq!(2.0, exp.lhs()[0].get_data());
+                assert_eq!(Relationship::LEQ, *exp.rel());
+                assert_eq!("y", exp.lhs()[1].name());
+                assert_eq!(3.0, exp.lhs()[1].get_data());
+                assert_eq!("volume", exp.rhs()[0].name());
+                assert_eq!(2300.0, exp.rhs()[0].get_data());
+                assert_eq!(Strength::Medium, strength);
+            }
+            _ => panic!("Unexpected variant."),
+        }
     }
 
     #[test]
@@ -118,7 +158,8 @@ mod tests {
                                   vec![new_const("volume", 2300.0)]);
         let c1 = new_reg_con(exp);
         let c2 = new_non_neg_con(new_var("x", 2.0));
-        let s = SystemOfConstraints::new(vec![c1, c2]);
+        let c3 = new_bounded_con(new_var("y", 1.0), -1.0, Some(4.0));
+        let s = SystemOfConstraints::new(vec![c1, c2, c3]);
         for constraint in s.system() {
             match constraint {
                 &Constraint::Regular(ref exp) => {
@@ -134,6 +175,12 @@ mod tests {
                     assert_eq!("x", abst_var.name());
                     assert_eq!(2.0, abst_var.get_data());
                 }
+                &Constraint::Bounded(ref abst_var, lower, upper) => {
+                    assert_eq!("y", abst_var.name());
+                    assert_eq!(-1.0, lower);
+                    assert_eq!(Some(4.0), upper);
+                }
+                &Constraint::Weighted(..) | &Constraint::Stay(_) => panic!("Unexpected variant."),
             }
         }
     }
@@ -160,11 +207,14 @@ mod tests {
         let mut s = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
         let fun = transform_constraint_rels_to_eq(&mut s).unwrap();
         assert_eq!("Expression { \
-                   lhs: [Variable { name: \"W\", coefficient: 1 }], \
+                   lhs: [Variable { name: \"W\", coefficient: 1.0 }], \
                    rel: EQ, \
-                   rhs: [Variable { name: \"k\", coefficient: 101 }, \
-                         Variable { name: \"c\", coefficient: 45 }, \
-                         Constant { name: \"RHS\", value: -500 }] }",
+                   rhs: [Variable { name: \"w\", coefficient: 6.0 }, \
+                         Variable { name: \"z\", coefficient: 9.0 }, \
+                         Variable { name: \"su2\", coefficient: -1.0 }, \
+                         Variable { name: \"k\", coefficient: 101.0 }, \
+                         Variable { name: \"c\", coefficient: 45.0 }, \
+                         Constant { name: \"RHS\", value: -800.0 }] }",
                    format!("{:?}", fun.exp_max()));
         match s.system()[0] {
             Constraint::Regular(ref exp) => {
@@ -173,7 +223,7 @@ mod tests {
                 assert_eq!(Relationship::EQ, *exp.rel());
                 assert_eq!("y", exp.lhs()[1].name());
                 assert_eq!(3.0, exp.lhs()[1].get_data());
-                assert_eq!(AbstVar::SlackVar { name: "sl1".to_string() }, exp.lhs()[2]);
+                assert_eq!(AbstVar::SlackVar { name: Name::from("sl1") }, exp.lhs()[2]);
                 assert_eq!("volume", exp.rhs()[0].name());
                 assert_eq!(2300.0, exp.rhs()[0].get_data());
             }
@@ -186,8 +236,9 @@ mod tests {
                 assert_eq!(Relationship::EQ, *exp.rel());
                 assert_eq!("z", exp.lhs()[1].name());
                 assert_eq!(9.0, exp.lhs()[1].get_data());
-                assert_eq!(AbstVar::SurplusVar { name: "su2".to_string() },
-                           exp.lhs()[2]);
+                assert_eq!(AbstVar::ArtiVar { name: Name::from("arti2") }, exp.lhs()[2]);
+                assert_eq!(AbstVar::SurplusVar { name: Name::from("su2") },
+                           exp.lhs()[3]);
                 assert_eq!("area", exp.rhs()[0].name());
                 assert_eq!(300.0, exp.rhs()[0].get_data());
             }
@@ -200,7 +251,7 @@ mod tests {
                 assert_eq!(Relationship::EQ, *exp.rel());
                 assert_eq!("t", exp.lhs()[1].name());
                 assert_eq!(-19.0, exp.lhs()[1].get_data());
-                assert_eq!(AbstVar::SlackVar { name: "sl3".to_string() }, exp.lhs()[2]);
+                assert_eq!(AbstVar::SlackVar { name: Name::from("sl3") }, exp.lhs()[2]);
                 assert_eq!("hyperplane", exp.rhs()[0].name());
                 assert_eq!(3000.0, exp.rhs()[0].get_data());
             }
@@ -213,7 +264,7 @@ mod tests {
                 assert_eq!(Relationship::EQ, *exp.rel());
                 assert_eq!("c", exp.lhs()[1].name());
                 assert_eq!(45.0, exp.lhs()[1].get_data());
-                assert_eq!(AbstVar::ArtiVar { name: "arti4".to_string() }, exp.lhs()[2]);
+                assert_eq!(AbstVar::ArtiVar { name: Name::from("arti4") }, exp.lhs()[2]);
                 assert_eq!("length", exp.rhs()[0].name());
                 assert_eq!(500.0, exp.rhs()[0].get_data());
             }
@@ -228,6 +279,79 @@ mod tests {
         };
     }
 
+    #[test]
+    fn can_add_error_vars() {
+        let e1 = Expression::new(vec![new_var("Z", 1.0)],
+                                 Relationship::EQ,
+                                 vec![new_var("x", 2.0), new_var("y", 3.0)]);
+        let mut f = Function::new(e1, ProblemType::MIN);
+        rearrange_fun_eq_zero(&mut f);
+        let exp1 = Expression::new(vec![new_var("x", 1.0)],
+                                   Relationship::LEQ,
+                                   vec![new_const("width", 30.0)]);
+        let exp2 = Expression::new(vec![new_var("y", 1.0)],
+                                   Relationship::GEQ,
+                                   vec![new_const("height", 10.0)]);
+        let exp3 = Expression::new(vec![new_var("x", 1.0), new_var("y", -1.0)],
+                                   Relationship::EQ,
+                                   vec![new_const("diff", 5.0)]);
+        let exp4 = Expression::new(vec![new_var("x", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_const("centre", 20.0)]);
+        let c1 = new_weighted_con(exp1, Strength::Strong);
+        let c2 = new_weighted_con(exp2, Strength::Weak);
+        let c3 = new_weighted_con(exp3, Strength::Custom(4.0));
+        let c4 = new_weighted_con(exp4, Strength::Required);
+        let c5 = new_non_neg_con(new_var("x", 1.0));
+        let mut s = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+        add_error_vars(&mut f, &mut s);
+        match s.system()[0] {
+            Constraint::Weighted(ref exp, _) => {
+                assert_eq!(2, exp.lhs().len());
+                assert_eq!(new_error_var("ep1".to_string(), -1.0), exp.lhs()[1]);
+                assert_eq!(-1.0, exp.lhs()[1].get_data());
+            }
+            _ => panic!("Unexpected variant in this program logic."),
+        };
+        match s.system()[1] {
+            Constraint::Weighted(ref exp, _) => {
+                assert_eq!(2, exp.lhs().len());
+                assert_eq!(new_error_var("em2".to_string(), 1.0), exp.lhs()[1]);
+                assert_eq!(1.0, exp.lhs()[1].get_data());
+            }
+            _ => panic!("Unexpected variant in this program logic."),
+        };
+        match s.system()[2] {
+            Constraint::Weighted(ref exp, _) => {
+                assert_eq!(4, exp.lhs().len());
+                assert_eq!("em3", exp.lhs()[2].name());
+                assert_eq!(1.0, exp.lhs()[2].get_data());
+                assert_eq!("ep3", exp.lhs()[3].name());
+                assert_eq!(-1.0, exp.lhs()[3].get_data());
+            }
+            _ => panic!("Unexpected variant in this program logic."),
+        };
+        match s.system()[3] {
+            Constraint::Weighted(ref exp, _) => assert_eq!(1, exp.lhs().len()),
+            _ => panic!("Unexpected variant in this program logic."),
+        };
+        let exp_max = f.exp_max();
+        assert_eq!("Q", exp_max.lhs()[2].name());
+        assert_eq!(AbstVar::ErrorVar {
+                       name: Name::from("em3"),
+                       coefficient: 4.0,
+                   },
+                   exp_max.lhs()[3]);
+        assert_eq!(4.0, exp_max.lhs()[3].get_data());
+        assert_eq!("ep3", exp_max.lhs()[4].name());
+        assert_eq!(4.0, exp_max.lhs()[4].get_data());
+        assert_eq!("em2", exp_max.lhs()[5].name());
+        assert_eq!(1.0, exp_max.lhs()[5].get_data());
+        assert_eq!("ep1", exp_max.lhs()[6].name());
+        assert_eq!(1_000_000.0, exp_max.lhs()[6].get_data());
+        assert_eq!(7, exp_max.lhs().len());
+    }
+
     #[test]
     fn can_rearrange_fun_eq_zero() {
         let e1 = Expression::new(vec![new_var("Z", 1.0)],
@@ -313,4 +437,30 @@ mod tests {
         assert_eq!("bonus", exp3_max.rhs()[0].name());
         assert_eq!(-1000.0, exp3_max.rhs()[0].get_data());
     }
+
+    #[test]
+    fn can_get_dual_markers() {
+        let exp1 = Expression::new(vec![new_var("x", 1.0)],
+                                   Relationship::LEQ,
+                                   vec![new_const("width", 30.0)]);
+        let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                                   Relationship::GEQ,
+                                   vec![new_const("width", 10.0)]);
+        let exp3 = Expression::new(vec![new_var("x", -1.0)],
+                                   Relationship::LEQ,
+                                   vec![new_const("width", -5.0)]);
+        let exp4 = Expression::new(vec![new_var("x", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_const("width", -20.0)]);
+        let s = SystemOfConstraints::new(vec![new_reg_con(exp1),
+                                              new_reg_con(exp2),
+                                              new_non_neg_con(new_var("x", 1.0)),
+                                              new_reg_con(exp3),
+                                              new_weighted_con(exp4, Strength::Weak)]);
+        assert_eq!(vec![(1, Name::from("sl1"), 1.0, 30.0),
+                        (2, Name::from("su2"), -1.0, 10.0),
+                        (4, Name::from("su4"), 1.0, -5.0),
+                        (5, Name::from("arti5"), -1.0, -20.0)],
+                   get_dual_markers(&s));
+    }
 }
@@ -1,4 +1,5 @@
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ProblemType {
     MAX,
     MIN,
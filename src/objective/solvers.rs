@@ -1,47 +1,67 @@
-use math::variables::{AbstVar, new_var, new_const, new_slack_var, new_surplus_var, new_arti_var};
+use math::variables::{AbstVar, Name, new_var, new_const, new_slack_var, new_surplus_var,
+                      new_arti_var, new_error_var};
 use math::expressions::Expression;
 use math::relationships::Relationship;
 use objective::functions::Function;
 use objective::problems::ProblemType;
 use objective::constraints::{Constraint, SystemOfConstraints};
+use Num;
 
-pub fn transform_constraint_rels_to_eq(constraints: &mut SystemOfConstraints) -> Option<Function> {
-    let mut phase1: Option<Expression> = None;
+pub fn transform_constraint_rels_to_eq<N: Num>(constraints: &mut SystemOfConstraints<N>)
+                                              -> Option<Function<N>> {
+    let mut phase1: Option<Expression<N>> = None;
     for (i, constraint) in constraints.system_mut().iter_mut().enumerate() {
         match constraint {
-            &mut Constraint::Regular(ref mut exp) => {
-                if exp.rhs()[0].get_data().is_sign_negative() {
+            &mut Constraint::Regular(ref mut exp) |
+            &mut Constraint::Weighted(ref mut exp, _) => {
+                if exp.rhs()[0].get_data() < N::zero() {
                     // Negative constants on the right hand side are not allowed.
-                    exp.mul_both_sides(-1.0);
+                    exp.mul_both_sides(-N::one());
                 }
-                match exp.rel() {
+                // Only a slack starts out with the value of a row it stays within, every other
+                // row has to be brought to its RHS by an artificial variable in Phase I.
+                let needs_arti_var = match exp.rel() {
                     &Relationship::LEQ => {
                         exp.add_lhs(new_slack_var(format!("{}{}", "sl", i + 1)));
                         exp.set_rel(Relationship::EQ);
+                        false
                     }
                     &Relationship::GEQ => {
                         exp.add_lhs(new_surplus_var(format!("{}{}", "su", i + 1)));
                         exp.set_rel(Relationship::EQ);
+                        true
                     }
-                    &Relationship::EQ => {
-                        // Build function for phase 1.
-                        if let Some(ref mut phase1_fun_exp) = phase1 {
-                            phase1_fun_exp.add_rhs(new_const("RHS",
-                                                             -1.0 * exp.rhs()[0].get_data()));
-                            for var in exp.lhs() {
-                                phase1_fun_exp.add_rhs(var.clone());
+                    &Relationship::EQ => true,
+                };
+                if needs_arti_var {
+                    // Build function for phase 1, with any surplus as a plain term of -1 so the
+                    // function can be rearranged like any other.
+                    let terms = exp.lhs()
+                        .iter()
+                        .map(|var| match *var {
+                            AbstVar::SurplusVar { ref name } => {
+                                AbstVar::Variable {
+                                    name: name.clone(),
+                                    coefficient: var.get_data(),
+                                }
                             }
-                        } else {
-                            let mut phase1_fun_exp = Expression::new(vec![new_var("W", 1.0)],
-                                                                     Relationship::EQ,
-                                                                     exp.lhs().clone());
-                            phase1_fun_exp.add_rhs(new_const("RHS",
-                                                             -1.0 * exp.rhs()[0].get_data()));
-                            phase1 = Some(phase1_fun_exp);
+                            _ => var.clone(),
+                        })
+                        .collect::<Vec<AbstVar<N>>>();
+                    if let Some(ref mut phase1_fun_exp) = phase1 {
+                        phase1_fun_exp.add_rhs(new_const("RHS", -exp.rhs()[0].get_data()));
+                        for var in terms {
+                            phase1_fun_exp.add_rhs(var);
                         }
-                        // Transform.
-                        exp.add_lhs(new_arti_var(format!("{}{}", "arti", i + 1)));
+                    } else {
+                        let mut phase1_fun_exp = Expression::new(vec![new_var("W", N::one())],
+                                                                 Relationship::EQ,
+                                                                 terms);
+                        phase1_fun_exp.add_rhs(new_const("RHS", -exp.rhs()[0].get_data()));
+                        phase1 = Some(phase1_fun_exp);
                     }
+                    // Transform.
+                    exp.add_lhs(new_arti_var(format!("{}{}", "arti", i + 1)));
                 }
             }
             _ => continue,
@@ -54,7 +74,66 @@ pub fn transform_constraint_rels_to_eq(constraints: &mut SystemOfConstraints) ->
     }
 }
 
-pub fn rearrange_fun_eq_zero(function: &mut Function) {
+pub fn add_error_vars<N: Num>(function: &mut Function<N>,
+                              constraints: &mut SystemOfConstraints<N>) {
+    for (i, constraint) in constraints.system_mut().iter_mut().enumerate() {
+        if let Constraint::Weighted(ref mut exp, ref strength) = *constraint {
+            if let Some(weight) = strength.weight() {
+                // Charge every unit of error to the function.
+                for error_var in get_error_vars(exp.rel(), i + 1) {
+                    function.exp_max_mut().add_lhs(AbstVar::ErrorVar {
+                                                       name: error_var.name().clone(),
+                                                       coefficient: weight.clone(),
+                                                   });
+                    exp.add_lhs(error_var);
+                }
+            }
+        }
+    }
+}
+
+pub fn get_dual_markers<N: Num>(constraints: &SystemOfConstraints<N>)
+                               -> Vec<(usize, Name, N, N)> {
+    // Pair every constraint with the column its dual value is read from once it is transformed,
+    // the sign that turns the entries of that column into those of the constraint as it was
+    // given (a surplus enters with -1 and a negated row flips it once more) and its RHS.
+    let mut markers = Vec::new();
+    for (i, constraint) in constraints.system().iter().enumerate() {
+        if let Constraint::Regular(ref exp) | Constraint::Weighted(ref exp, _) = *constraint {
+            let flipped = exp.rhs()[0].get_data() < N::zero();
+            let rel = match (exp.rel(), flipped) {
+                (&Relationship::LEQ, true) => Relationship::GEQ,
+                (&Relationship::GEQ, true) => Relationship::LEQ,
+                (rel, _) => rel.clone(),
+            };
+            let (marker, sign) = match rel {
+                Relationship::LEQ => (format!("{}{}", "sl", i + 1), N::one()),
+                Relationship::GEQ => (format!("{}{}", "su", i + 1), -N::one()),
+                Relationship::EQ => (format!("{}{}", "arti", i + 1), N::one()),
+            };
+            markers.push((i + 1,
+                          Name::from(marker),
+                          if flipped { -sign } else { sign },
+                          exp.rhs()[0].get_data()));
+        }
+    }
+    markers
+}
+
+pub fn get_error_vars<N: Num>(rel: &Relationship, id: usize) -> Vec<AbstVar<N>> {
+    // Let the left hand side overshoot (ep) and/or undershoot (em) the right hand side as far
+    // as the relationship would otherwise forbid it.
+    let mut error_vars = Vec::with_capacity(2);
+    if *rel != Relationship::GEQ {
+        error_vars.push(new_error_var(format!("{}{}", "ep", id), -N::one()));
+    }
+    if *rel != Relationship::LEQ {
+        error_vars.push(new_error_var(format!("{}{}", "em", id), N::one()));
+    }
+    error_vars
+}
+
+pub fn rearrange_fun_eq_zero<N: Num>(function: &mut Function<N>) {
     let mut exp = function.exp_max_mut();
     exp.move_from_lhs_side(0, false);
     exp.swap_sides().unwrap();
@@ -66,5 +145,5 @@ pub fn rearrange_fun_eq_zero(function: &mut Function) {
     if let Some(found_index) = search {
         exp.move_from_lhs_side(found_index, false);
     }
-    exp.mul_both_sides(-1.0);
+    exp.mul_both_sides(-N::one());
 }
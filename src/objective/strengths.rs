@@ -0,0 +1,31 @@
+use Num;
+
+/// How hard the solver holds on to a constraint.
+///
+/// Every strength but `Required` is a plain weight on the error of its constraint in a single
+/// objective, not a strict priority: enough error in weaker constraints, such as 1001 units of
+/// medium error, outweighs one unit of strong error. The weights also span six orders of
+/// magnitude, so next to strong errors a weak one can drop below the rounding of `f32`; models
+/// that mix them are better solved with `f64` or `Rational`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Strength<N> {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+    Custom(N),
+}
+
+impl<N: Num> Strength<N> {
+    /// Weight of a unit of error in the objective, `None` for constraints that must hold.
+    pub fn weight(&self) -> Option<N> {
+        match *self {
+            Strength::Required => None,
+            Strength::Strong => Some(N::from_i32(1_000_000)),
+            Strength::Medium => Some(N::from_i32(1_000)),
+            Strength::Weak => Some(N::one()),
+            Strength::Custom(ref weight) => Some(weight.clone()),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tableau::tolerances::Tolerances;
+use tableau::pivot_rules::{PivotRule, Dantzig};
+use Num;
+
+/// Settings that tune how a model is solved without changing the model itself.
+#[derive(Debug, Clone)]
+pub struct Options<N> {
+    tolerances: Tolerances<N>,
+    pivot_rule: Box<dyn PivotRule<N>>,
+    degenerate_pivot_limit: Option<usize>,
+    iteration_limit: Option<usize>,
+    time_limit: Option<Duration>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl<N: Num> Options<N> {
+    pub fn tolerances(&self) -> &Tolerances<N> {
+        &self.tolerances
+    }
+
+    pub fn pivot_rule(&self) -> &dyn PivotRule<N> {
+        &*self.pivot_rule
+    }
+
+    pub fn degenerate_pivot_limit(&self) -> Option<usize> {
+        self.degenerate_pivot_limit
+    }
+
+    pub fn iteration_limit(&self) -> Option<usize> {
+        self.iteration_limit
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    pub fn set_tolerances(&mut self, tolerances: Tolerances<N>) {
+        self.tolerances = tolerances;
+    }
+
+    pub fn set_pivot_rule<R>(&mut self, pivot_rule: R)
+        where R: PivotRule<N> + 'static
+    {
+        self.pivot_rule = Box::new(pivot_rule);
+    }
+
+    /// After this many pivots in a row that leave the function where it was, Bland's rule takes
+    /// over until one moves it again, in Phase I as much as in Phase II. `None` keeps to the
+    /// chosen rule no matter what.
+    pub fn set_degenerate_pivot_limit(&mut self, limit: Option<usize>) {
+        self.degenerate_pivot_limit = limit;
+    }
+
+    /// The most pivots a solve may take, Phase I included, before it stops where it got to.
+    /// `None` lets it run until it is done.
+    pub fn set_iteration_limit(&mut self, limit: Option<usize>) {
+        self.iteration_limit = limit;
+    }
+
+    /// How long a solve may run, counted from when it starts, before it stops where it got to.
+    pub fn set_time_limit(&mut self, limit: Option<Duration>) {
+        self.time_limit = limit;
+    }
+
+    /// Solves watch the token between pivots and stop where they got to once it is cancelled.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.cancellation_token = token;
+    }
+}
+
+impl<N: Num> Default for Options<N> {
+    fn default() -> Options<N> {
+        Options {
+            tolerances: Tolerances::default(),
+            pivot_rule: Box::new(Dantzig),
+            degenerate_pivot_limit: Some(50),
+            iteration_limit: None,
+            time_limit: None,
+            cancellation_token: None,
+        }
+    }
+}
+
+/// A flag shared between a solve and whoever may want to stop it, on this thread or another.
+/// Clones all share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,210 @@
+use Num;
+use math::variables::{Name, VarKey};
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Status {
+    /// No other solution reaches the same objective value from this basis.
+    Optimal,
+    /// Optimal, but some non-basic variable could enter without changing the objective value
+    /// so other solutions may reach it too.
+    AlternativeOptima,
+    /// Feasible, but the solve ran out of pivots before it could show the basis is optimal.
+    IterationLimit,
+    /// Feasible, but the solve ran out of time before it could show the basis is optimal.
+    TimeLimit,
+    /// Feasible, but the solve was cancelled before it could show the basis is optimal.
+    Cancelled,
+}
+
+/// An interval of values, `None` at either end where it is unbounded.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Range<N> {
+    lower: Option<N>,
+    upper: Option<N>,
+}
+
+impl<N: Num> Range<N> {
+    pub fn new(lower: Option<N>, upper: Option<N>) -> Range<N> {
+        Range { lower, upper }
+    }
+
+    pub fn lower(&self) -> Option<N> {
+        self.lower.clone()
+    }
+
+    pub fn upper(&self) -> Option<N> {
+        self.upper.clone()
+    }
+
+    pub fn contains(&self, value: N) -> bool {
+        self.lower.as_ref().is_none_or(|lower| *lower <= value) &&
+        self.upper.as_ref().is_none_or(|upper| value <= *upper)
+    }
+}
+
+/// What the final table says about how the solution responds to changes in the model.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sensitivity<N> {
+    reduced_costs: Vec<(Name, N)>,
+    duals: Vec<(usize, N)>,
+    cost_ranges: Vec<(Name, Range<N>)>,
+    rhs_ranges: Vec<(usize, Range<N>)>,
+    // The entries of the reduced costs and cost ranges in the order of their names.
+    reduced_cost_order: Vec<usize>,
+    cost_range_order: Vec<usize>,
+}
+
+impl<N: Num> Sensitivity<N> {
+    pub fn new(reduced_costs: Vec<(Name, N)>,
+               duals: Vec<(usize, N)>,
+               cost_ranges: Vec<(Name, Range<N>)>,
+               rhs_ranges: Vec<(usize, Range<N>)>)
+               -> Sensitivity<N> {
+        Sensitivity {
+            reduced_cost_order: get_order(&reduced_costs),
+            cost_range_order: get_order(&cost_ranges),
+            reduced_costs,
+            duals,
+            cost_ranges,
+            rhs_ranges,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Solution<N> {
+    values: Vec<(Name, N)>,
+    internals: Vec<(Name, N)>,
+    objective: (Name, N),
+    sensitivity: Sensitivity<N>,
+    status: Status,
+    iterations: usize,
+    // The values in the order of their names, so a variable is found without going through
+    // them all.
+    value_order: Vec<usize>,
+}
+
+impl<N: Num> Solution<N> {
+    pub fn new(values: Vec<(Name, N)>,
+               internals: Vec<(Name, N)>,
+               objective: (Name, N),
+               sensitivity: Sensitivity<N>,
+               status: Status,
+               iterations: usize)
+               -> Solution<N> {
+        Solution {
+            value_order: get_order(&values),
+            values,
+            internals,
+            objective,
+            sensitivity,
+            status,
+            iterations,
+        }
+    }
+
+    /// Every decision variable in the order the table holds them, non-basic ones at zero.
+    pub fn values(&self) -> &Vec<(Name, N)> {
+        &self.values
+    }
+
+    /// How much the objective value changes per unit of each decision variable forced into the
+    /// solution, zero for basic ones. Its coefficient in the function has to improve by as
+    /// much before the variable enters the basis.
+    pub fn reduced_costs(&self) -> &Vec<(Name, N)> {
+        &self.sensitivity.reduced_costs
+    }
+
+    pub fn reduced_cost<K: VarKey + ?Sized>(&self, var: &K) -> Option<N> {
+        find_var_value(&self.sensitivity.reduced_costs,
+                       &self.sensitivity.reduced_cost_order,
+                       var)
+    }
+
+    /// The values each decision variable's coefficient in the function can take while the
+    /// current basis stays optimal.
+    pub fn cost_ranges(&self) -> &Vec<(Name, Range<N>)> {
+        &self.sensitivity.cost_ranges
+    }
+
+    pub fn cost_range<K: VarKey + ?Sized>(&self, var: &K) -> Option<Range<N>> {
+        find_var_value(&self.sensitivity.cost_ranges, &self.sensitivity.cost_range_order, var)
+    }
+
+    /// The slack, surplus, artificial and error variables the solver introduced.
+    pub fn internals(&self) -> &Vec<(Name, N)> {
+        &self.internals
+    }
+
+    pub fn value<K: VarKey + ?Sized>(&self, var: &K) -> Option<N> {
+        find_var_value(&self.values, &self.value_order, var)
+    }
+
+    pub fn internal_value(&self, name: &str) -> Option<N> {
+        find_value(&self.internals, name)
+    }
+
+    pub fn objective_name(&self) -> &Name {
+        &self.objective.0
+    }
+
+    pub fn objective(&self) -> N {
+        self.objective.1.clone()
+    }
+
+    /// The rate at which the objective value changes per unit increase of the RHS of each
+    /// constraint, keyed by constraint number.
+    pub fn duals(&self) -> &Vec<(usize, N)> {
+        &self.sensitivity.duals
+    }
+
+    pub fn dual(&self, constraint: usize) -> Option<N> {
+        find_value(&self.sensitivity.duals, &constraint)
+    }
+
+    /// The values the RHS of each constraint can take while its dual value stays valid.
+    pub fn rhs_ranges(&self) -> &Vec<(usize, Range<N>)> {
+        &self.sensitivity.rhs_ranges
+    }
+
+    pub fn rhs_range(&self, constraint: usize) -> Option<Range<N>> {
+        find_value(&self.sensitivity.rhs_ranges, &constraint)
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// The number of pivots it took to get here, Phase I included.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+fn find_value<K, Q, V>(entries: &[(K, V)], key: &Q) -> Option<V>
+    where K: PartialEq<Q>,
+          Q: ?Sized,
+          V: Clone
+{
+    entries.iter().find(|entry| entry.0 == *key).map(|entry| entry.1.clone())
+}
+
+fn get_order<V>(entries: &[(Name, V)]) -> Vec<usize> {
+    let mut order = (0..entries.len()).collect::<Vec<usize>>();
+    order.sort_by(|&i, &j| entries[i].0.cmp(&entries[j].0));
+    order
+}
+
+fn find_var_value<K, V>(entries: &[(Name, V)], order: &[usize], var: &K) -> Option<V>
+    where K: VarKey + ?Sized,
+          V: Clone
+{
+    let name = var.var_key();
+    order.binary_search_by(|&i| entries[i].0.cmp(&name))
+        .ok()
+        .map(|position| entries[order[position]].1.clone())
+}
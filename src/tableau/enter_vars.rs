@@ -1,28 +1,126 @@
 use tableau::tables::Table;
+use Num;
 
-pub fn enter_var_pivot_optimal(table: &Table) -> usize {
+pub fn enter_var_pivot_optimal<N: Num>(table: &Table<N>) -> Option<usize> {
     let table_rows = table.get_rows();
     let last_row_index = table_rows.len() - 1;
+    let excluded_columns = table.get_excluded_columns();
     // Select the most negative cell in the objective function row.
-    let mut column_index = 0;
+    let mut column_index = None;
     for i in 0..table_rows[last_row_index].len() - 1 {
-        if table_rows[last_row_index][i] < table_rows[last_row_index][column_index] {
-            column_index = i;
+        if excluded_columns.contains(&i) || table.is_basic(i) {
+            continue;
+        }
+        match column_index {
+            Some(current) if table_rows[last_row_index][current] <=
+                             table_rows[last_row_index][i] => {}
+            _ => column_index = Some(i),
         }
     }
     column_index
 }
 
-pub fn enter_var_pivot_feasible(table: &Table,
-                                row: usize,
-                                begin_column: usize)
-                                -> Option<usize> {
+pub fn enter_var_bland<N: Num>(table: &Table<N>) -> Option<usize> {
+    // Select the improving column furthest to the left.
+    get_improving_columns(table).into_iter().next()
+}
+
+pub fn enter_var_steepest_edge<N: Num>(table: &Table<N>) -> Option<usize> {
+    let table_rows = table.get_rows();
+    let function_row = &table_rows[table_rows.len() - 1];
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    // Select the column that improves the function the most per unit of distance moved, which
+    // compares squared reduced costs to the squared length of the edge each column leads along.
+    let weights = (0..function_row.len() - 1)
+        .map(|column| {
+            table_rows.iter()
+                .take(num_constraint_rows)
+                .fold(N::one(), |sum, row| sum + row[column].clone() * row[column].clone())
+        })
+        .collect::<Vec<N>>();
+    enter_var_weighted(table, &weights)
+}
+
+pub fn enter_var_weighted<N: Num>(table: &Table<N>, weights: &[N]) -> Option<usize> {
+    let table_rows = table.get_rows();
+    let function_row = &table_rows[table_rows.len() - 1];
+    // Select the improving column with the largest squared reduced cost for its weight.
+    let mut entering: Option<(usize, N)> = None;
+    for column in get_improving_columns(table) {
+        let score = function_row[column].clone() * function_row[column].clone() /
+                    weights[column].clone();
+        match entering {
+            Some((_, ref score_current)) if *score_current >= score => {}
+            _ => entering = Some((column, score)),
+        }
+    }
+    entering.map(|(column_index, _)| column_index)
+}
+
+pub fn enter_var_partial<N: Num>(table: &Table<N>,
+                                 start: usize,
+                                 segment_size: usize)
+                                 -> Option<(usize, usize)> {
     let table_rows = table.get_rows();
-    // Select the positive cell furthest to the left.
-    for i in 0..begin_column {
-        if table_rows[row][i].is_sign_positive() && table_rows[row][i] > 0.0 {
-            return Some(i);
+    let function_row = &table_rows[table_rows.len() - 1];
+    let num_columns = function_row.len() - 1;
+    let improving_columns = get_improving_columns(table);
+    // Look at one segment of columns at a time, going round from where the last search ended,
+    // and select the most negative cell of the first segment that has any improving column.
+    // Report where the next search should start too.
+    let segment_size = segment_size.max(1);
+    let mut segment_start = start % num_columns.max(1);
+    for _ in 0..num_columns.div_ceil(segment_size) {
+        let segment = (0..segment_size.min(num_columns))
+            .map(|offset| (segment_start + offset) % num_columns)
+            .collect::<Vec<usize>>();
+        segment_start = (segment_start + segment_size) % num_columns;
+        let mut entering: Option<usize> = None;
+        for column in segment {
+            if !improving_columns.contains(&column) {
+                continue;
+            }
+            match entering {
+                Some(current) if function_row[current] <= function_row[column] => {}
+                _ => entering = Some(column),
+            }
+        }
+        if let Some(column_index) = entering {
+            return Some((column_index, segment_start));
         }
     }
     None
 }
+
+pub fn enter_var_dual<N: Num>(row: usize, table: &Table<N>) -> Option<usize> {
+    let table_rows = table.get_rows();
+    let last_row_index = table_rows.len() - 1;
+    let excluded_columns = table.get_excluded_columns();
+    // Among the negative cells of the leaving row select the one with the smallest ratio of
+    // the function row entry to it, so the function row stays non-negative after the pivot.
+    let mut entering: Option<(usize, N)> = None;
+    let last_column_index = table_rows[row].len() - 1;
+    for (i, cell) in table_rows[row].iter().enumerate().take(last_column_index) {
+        if excluded_columns.contains(&i) || !table.get_tolerances().is_negative(cell) {
+            continue;
+        }
+        let ratio = table_rows[last_row_index][i].clone() / -cell.clone();
+        match entering {
+            Some((_, ref ratio_current)) if *ratio_current <= ratio => {}
+            _ => entering = Some((i, ratio)),
+        }
+    }
+    entering.map(|(column_index, _)| column_index)
+}
+
+fn get_improving_columns<N: Num>(table: &Table<N>) -> Vec<usize> {
+    let table_rows = table.get_rows();
+    let function_row = &table_rows[table_rows.len() - 1];
+    let excluded_columns = table.get_excluded_columns();
+    (0..function_row.len() - 1)
+        .filter(|&i| {
+            !excluded_columns.contains(&i) && !table.is_basic(i) &&
+            table.get_tolerances().is_improving_cost(&function_row[i])
+        })
+        .collect()
+}
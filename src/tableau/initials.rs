@@ -1,11 +1,16 @@
 use std::collections::HashMap;
 use Num;
+use math::variables::{AbstVar, Name};
+use math::expressions::Expression;
 use objective::functions::Function;
+use objective::problems::ProblemType;
 use objective::constraints::{Constraint, SystemOfConstraints};
-use tableau::tables::Table;
+use tableau::tables::{Table, ColumnKind};
 
-pub fn get_initial_table_from(fun: &Function, constraints: &SystemOfConstraints) -> Table {
-    let mut column_names: HashMap<String, usize> = HashMap::new();
+pub fn get_initial_table_from<N: Num>(fun: &Function<N>,
+                                     constraints: &SystemOfConstraints<N>)
+                                     -> Table<N> {
+    let mut column_names: HashMap<Name, usize> = HashMap::new();
     // We have number of rows equal to the number of regular constraints plus a
     // row for the function we are maxising or minimising.
     let mut num_rows: usize = 0;
@@ -13,28 +18,33 @@ pub fn get_initial_table_from(fun: &Function, constraints: &SystemOfConstraints)
     // with respect to a variable name.
     for constraint in constraints.system() {
         match constraint {
-            &Constraint::Regular(ref exp) => {
+            &Constraint::Regular(ref exp) |
+            &Constraint::Weighted(ref exp, _) => {
                 num_rows += 1;
                 for var in exp.lhs() {
-                    insert_column_name_not_present(var.name().to_string(), &mut column_names);
+                    insert_column_name_not_present(var.name().clone(), &mut column_names);
                 }
             }
-            &Constraint::NonNegative(_) => continue,
+            &Constraint::NonNegative(_) |
+            &Constraint::Bounded(..) |
+            &Constraint::Stay(_) => continue,
         }
     }
     for var in fun.exp_max().lhs() {
-        insert_column_name_not_present(var.name().to_string(), &mut column_names);
+        insert_column_name_not_present(var.name().clone(), &mut column_names);
     }
     // ... and don't forget about the constant on the right.
     let map_len = column_names.len();
-    column_names.insert("RHS".to_string(), map_len);
+    column_names.insert(Name::from("RHS"), map_len);
 
-    let mut rows: Vec<Vec<Num>> = vec![vec![0.0; column_names.len()]; num_rows];
+    let mut rows: Vec<Vec<N>> = vec![vec![N::zero(); column_names.len()]; num_rows];
     // Populate the table
     let mut row_index = 0;
+    let mut basic_columns = Vec::with_capacity(num_rows);
     for constraint in constraints.system() {
         match constraint {
-            &Constraint::Regular(ref exp) => {
+            &Constraint::Regular(ref exp) |
+            &Constraint::Weighted(ref exp, _) => {
                 for var in exp.lhs() {
                     rows[row_index]
                         [*column_names.get(var.name())
@@ -44,27 +54,70 @@ pub fn get_initial_table_from(fun: &Function, constraints: &SystemOfConstraints)
                 // ... and don't forget about the constant on the right.
                 let last_column = rows[row_index].len() - 1;
                 rows[row_index][last_column] = exp.rhs()[0].get_data();
+                basic_columns.push(get_basic_var_name(exp).map(|name| column_names[name]));
                 row_index += 1;
             }
-            &Constraint::NonNegative(_) => continue,
+            &Constraint::NonNegative(_) |
+            &Constraint::Bounded(..) |
+            &Constraint::Stay(_) => continue,
         }
     }
     rows.push(get_row_for_function(fun, &column_names));
+    let fun_row = rows.len() - 1;
+    let fun_column = column_names[&get_fun_column_name(fun)];
 
-    Table::new(column_names, rows)
+    let mut table = Table::new(column_names, rows);
+    // Every row starts out with the variable it was built with as basic, whatever else looks
+    // like a unit column there, and so does the function variable in its own row.
+    for (row, basic_column) in basic_columns.into_iter().enumerate() {
+        if let Some(column) = basic_column {
+            table.set_basic_var_of_row(row, column);
+        }
+    }
+    table.set_basic_var_of_row(fun_row, fun_column);
+    // Mark the slack, surplus, error and artificial columns by the variables they came from.
+    let constraint_vars = constraints.system().iter().flat_map(|constraint| match constraint {
+        &Constraint::Regular(ref exp) |
+        &Constraint::Weighted(ref exp, _) => exp.lhs().iter(),
+        _ => [].iter(),
+    });
+    for var in constraint_vars.chain(fun.exp_max().lhs()) {
+        let column = table.get_column_names()[var.name()];
+        table.set_column_kind(column, ColumnKind::of(var));
+    }
+    table
 }
 
-pub fn append_function(fun: &Function, to_table: &mut Table) {
-    to_table.append_empty_column(fun.name().to_string());
+pub fn append_function<N: Num>(fun: &Function<N>, to_table: &mut Table<N>) {
+    to_table.append_empty_column(fun.name());
     let row_to_append = get_row_for_function(fun, to_table.get_column_names());
     to_table.append_row(row_to_append);
+    // The function variable is basic in its own row.
+    let fun_column = to_table.get_column_names()[&get_fun_column_name(fun)];
+    let fun_row = to_table.get_rows().len() - 1;
+    to_table.set_basic_var_of_row(fun_row, fun_column);
     let old_num_fun_rows = to_table.get_num_fun_rows();
     to_table.set_num_fun_rows(old_num_fun_rows + 1);
 }
 
-fn get_row_for_function(fun: &Function, c_n: &HashMap<String, usize>) -> Vec<Num> {
+// The artificial variable of a row if it has one, which Phase I works with, or else its slack
+// or surplus.
+fn get_basic_var_name<N: Num>(exp: &Expression<N>) -> Option<&Name> {
+    exp.lhs()
+        .iter()
+        .filter_map(|var| match *var {
+            AbstVar::ArtiVar { .. } => Some((0, var)),
+            AbstVar::SlackVar { .. } => Some((1, var)),
+            AbstVar::SurplusVar { .. } => Some((2, var)),
+            _ => None,
+        })
+        .min_by_key(|&(preference, _)| preference)
+        .map(|(_, var)| var.name())
+}
+
+fn get_row_for_function<N: Num>(fun: &Function<N>, c_n: &HashMap<Name, usize>) -> Vec<N> {
     let fun_exp = fun.exp_max();
-    let mut fun_row = vec![0.0; c_n.len()];
+    let mut fun_row = vec![N::zero(); c_n.len()];
     for var in fun_exp.lhs() {
         fun_row[*c_n.get(var.name())
                 .expect("get_row_for_function: variable name key not present.")] =
@@ -75,7 +128,15 @@ fn get_row_for_function(fun: &Function, c_n: &HashMap<String, usize>) -> Vec<Num
     fun_row
 }
 
-fn insert_column_name_not_present(name: String, into: &mut HashMap<String, usize>) {
+fn get_fun_column_name<N: Num>(fun: &Function<N>) -> Name {
+    // A function to minimise is solved as "Q" = -function to maximise.
+    match *fun.p_type() {
+        ProblemType::MIN => Name::from("Q"),
+        ProblemType::MAX => fun.name(),
+    }
+}
+
+fn insert_column_name_not_present(name: Name, into: &mut HashMap<Name, usize>) {
     let map_len = into.len();
     into.entry(name).or_insert(map_len);
 }
@@ -1,37 +1,218 @@
+use math::variables::Name;
 use tableau::tables::Table;
+use Num;
 
-pub fn leave_var(enter_var_index: usize, table: &Table) -> usize {
-    let table_rows = table.get_rows();
-    let last_column_index = table.get_column_names().len() - 1;
+/// What bringing in a column comes down to once the bounds of the variables have their say.
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    /// The basic variable of the row drops to its lower bound and leaves.
+    Pivot(usize),
+    /// The basic variable of the row climbs to its upper bound and leaves.
+    PivotAtUpper(usize),
+    /// The entering variable reaches its own upper bound first and nothing leaves.
+    Flip,
+}
+
+pub fn leave_var<N: Num>(enter_var_index: usize, table: &Table<N>) -> Option<usize> {
     // Pick according to the smallest positive ratio of the entry in the
     // RHS column and the corresponding entry in pivot column.
-    let mut row_index = 0;
-    let mut ratio_current = table_rows[row_index][last_column_index] /
-                            table_rows[row_index][enter_var_index];
-    let mut i = 1;
-    // Do not consider RHS of the function rows as we do not pivot on it.
-    'down_columns: while i < table_rows.len() - table.get_num_fun_rows() {
-        if ratio_current.is_sign_negative() {
-            row_index = row_index + 1;
-            ratio_current = table_rows[row_index][last_column_index] /
-                            table_rows[row_index][enter_var_index];
-            continue 'down_columns;
-        }
-        // Find the next positive ratio
-        let mut ratio_next = table_rows[i][last_column_index] / table_rows[i][enter_var_index];
-        while ratio_next.is_sign_negative() {
-            i = i + 1;
-            if i > table_rows.len() - table.get_num_fun_rows() {
-                break 'down_columns;
+    let mut leaving: Option<(usize, N)> = None;
+    for (i, ratio) in get_ratios(enter_var_index, table) {
+        match leaving {
+            Some((_, ref ratio_current)) if *ratio_current <= ratio => {}
+            _ => leaving = Some((i, ratio)),
+        }
+    }
+    // No row limits the entering variable so the problem is unbounded.
+    leaving.map(|(row_index, _)| row_index)
+}
+
+pub fn leave_var_bland<N: Num>(enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+    // Among the rows tied for the smallest ratio pick the one whose basic variable comes first.
+    get_tied_rows(enter_var_index, table)
+        .into_iter()
+        .min_by_key(|row| table.get_basic_var_of_row(*row).unwrap_or(usize::MAX))
+}
+
+pub fn leave_var_lexicographic<N: Num>(enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+    let table_rows = table.get_rows();
+    let tolerances = table.get_tolerances();
+    let last_column_index = table.get_column_names().len() - 1;
+    // Break ties between the rows with the smallest ratio by comparing the rest of them, scaled
+    // by their pivot column entry, one column at a time. No two rows of a table are parallel so
+    // this always settles on a single row.
+    let mut leaving: Option<usize> = None;
+    for row in get_tied_rows(enter_var_index, table) {
+        let current = match leaving {
+            Some(current) => current,
+            None => {
+                leaving = Some(row);
+                continue;
+            }
+        };
+        for column in 0..last_column_index {
+            let scaled = table_rows[row][column].clone() /
+                         table_rows[row][enter_var_index].clone();
+            let scaled_current = table_rows[current][column].clone() /
+                                 table_rows[current][enter_var_index].clone();
+            if !tolerances.is_zero(&(scaled.clone() - scaled_current.clone())) {
+                if scaled < scaled_current {
+                    leaving = Some(row);
+                }
+                break;
             }
-            ratio_next = table_rows[i][last_column_index] / table_rows[i][enter_var_index];
         }
-        if ratio_next < ratio_current {
-            row_index = i;
-            ratio_current = table_rows[row_index][last_column_index] /
-                            table_rows[row_index][enter_var_index];
+    }
+    leaving
+}
+
+pub fn leave_var_bounded<N: Num>(enter_var_index: usize,
+                                 rule_row: Option<usize>,
+                                 table: &Table<N>)
+                                 -> Option<Step> {
+    let table_rows = table.get_rows();
+    let last_column_index = table.get_column_names().len() - 1;
+    let tolerances = table.get_tolerances();
+    // The row the pivot rule chose stops the entering variable where its basic variable drops
+    // to zero. Only a strictly closer upper bound gets to take over from it.
+    let mut step: Option<(Step, N)> = rule_row.map(|row| {
+        let rhs = table_rows[row][last_column_index].clone();
+        let ratio = if tolerances.is_zero(&rhs) {
+            N::zero()
+        } else {
+            rhs / table_rows[row][enter_var_index].clone()
+        };
+        (Step::Pivot(row), ratio)
+    });
+    let mut candidates = Vec::new();
+    if let Some(width) = table.get_width(enter_var_index) {
+        candidates.push((Step::Flip, width));
+    }
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    for (i, row) in table_rows.iter().enumerate().take(num_constraint_rows) {
+        // A basic variable grows as the entering one does where the pivot column is negative.
+        if !tolerances.is_negative(&row[enter_var_index]) {
+            continue;
+        }
+        let width = match table.get_basic_var_of_row(i).and_then(|column| table.get_width(column)) {
+            Some(width) => width,
+            None => continue,
+        };
+        let room = width - row[last_column_index].clone();
+        let ratio = if tolerances.is_positive(&room) {
+            room / -row[enter_var_index].clone()
+        } else {
+            N::zero()
+        };
+        candidates.push((Step::PivotAtUpper(i), ratio));
+    }
+    for (candidate, ratio) in candidates {
+        match step {
+            Some((_, ref ratio_current)) if *ratio_current <= ratio => {}
+            _ => step = Some((candidate, ratio)),
+        }
+    }
+    step.map(|(step, _)| step)
+}
+
+pub fn get_unbounded_ray<N: Num>(enter_var_index: usize, table: &Table<N>) -> Vec<(Name, N)> {
+    let table_rows = table.get_rows();
+    let last_column_index = table.get_column_names().len() - 1;
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    let mut columns = table.get_column_names()
+        .iter()
+        .filter(|&(_, index)| *index != last_column_index)
+        .collect::<Vec<(&Name, &usize)>>();
+    columns.sort_by_key(|&(_, index)| *index);
+    // Increasing the entering variable by one unit moves every basic variable by minus its
+    // entry in the pivot column while the other non-basic variables stay at zero. Complemented
+    // columns count the other way.
+    let direction = |column: usize, step: N| {
+        if table.is_complemented(column) { -step } else { step }
+    };
+    let mut ray = Vec::new();
+    for (name, &column) in columns {
+        if column == enter_var_index {
+            ray.push((name.clone(), direction(column, N::one())));
+        } else if let Ok(row) = table.get_row_of_basic_var(name) {
+            if row < num_constraint_rows &&
+               !table.get_tolerances().is_zero(&table_rows[row][enter_var_index]) {
+                ray.push((name.clone(),
+                          direction(column, -table_rows[row][enter_var_index].clone())));
+            }
+        }
+    }
+    ray
+}
+
+pub fn leave_var_dual<N: Num>(table: &Table<N>) -> Option<usize> {
+    let table_rows = table.get_rows();
+    let last_column_index = table.get_column_names().len() - 1;
+    // Pick the constraint row with the most negative RHS, if any is left.
+    let mut leaving: Option<(usize, N)> = None;
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    for (i, row) in table_rows.iter().enumerate().take(num_constraint_rows) {
+        let rhs = &row[last_column_index];
+        if !table.get_tolerances().is_negative(rhs) {
+            continue;
+        }
+        match leaving {
+            Some((_, ref rhs_current)) if rhs_current <= rhs => {}
+            _ => leaving = Some((i, rhs.clone())),
+        }
+    }
+    leaving.map(|(row_index, _)| row_index)
+}
+
+fn get_ratios<N: Num>(enter_var_index: usize, table: &Table<N>) -> Vec<(usize, N)> {
+    let table_rows = table.get_rows();
+    let last_column_index = table.get_column_names().len() - 1;
+    let tolerances = table.get_tolerances();
+    let mut ratios = Vec::new();
+    // Do not consider RHS of the function rows as we do not pivot on it.
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    for (i, row) in table_rows.iter().enumerate().take(num_constraint_rows) {
+        let pivot_column_entry = row[enter_var_index].clone();
+        if tolerances.is_zero(&pivot_column_entry) {
+            continue;
+        }
+        // A zero RHS only blocks the entering variable when the pivot column entry is
+        // positive, whichever sign the zero happens to carry, in the row as its basic variable
+        // sees it: a surplus that is basic with -1 sees every entry of its row negated.
+        let rhs = row[last_column_index].clone();
+        let basic_sign = match table.get_basic_var_of_row(i) {
+            Some(column) if row[column] < N::zero() => -N::one(),
+            _ => N::one(),
+        };
+        if tolerances.is_zero(&rhs) && basic_sign * pivot_column_entry.clone() < N::zero() {
+            continue;
+        }
+        let ratio = if tolerances.is_zero(&rhs) { N::zero() } else { rhs / pivot_column_entry };
+        if ratio < N::zero() {
+            continue;
+        }
+        ratios.push((i, ratio));
+    }
+    ratios
+}
+
+fn get_tied_rows<N: Num>(enter_var_index: usize, table: &Table<N>) -> Vec<usize> {
+    let ratios = get_ratios(enter_var_index, table);
+    let mut smallest: Option<&N> = None;
+    for (_, ratio) in &ratios {
+        if smallest.is_none_or(|current| ratio < current) {
+            smallest = Some(ratio);
+        }
+    }
+    match smallest {
+        Some(smallest) => {
+            ratios.iter()
+                .filter(|&(_, ratio)| {
+                    table.get_tolerances().is_zero(&(ratio.clone() - smallest.clone()))
+                })
+                .map(|&(row, _)| row)
+                .collect()
         }
-        i = i + 1;
+        None => Vec::new(),
     }
-    row_index
 }
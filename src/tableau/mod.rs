@@ -3,22 +3,31 @@ pub mod initials;
 pub mod enter_vars;
 pub mod leave_vars;
 pub mod pivots;
+pub mod ranges;
+pub mod pivot_rules;
+pub mod tolerances;
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use math::variables::{new_var, new_const};
+    use math::variables::{Name, new_var, new_const};
     use math::relationships::Relationship;
     use math::expressions::Expression;
     use objective::problems::ProblemType;
     use objective::functions::Function;
     use objective::constraints::{SystemOfConstraints, new_reg_con, new_non_neg_con};
     use objective::solvers::{transform_constraint_rels_to_eq, rearrange_fun_eq_zero};
-    use tableau::tables::Table;
+    use tableau::tables::{Table, ColumnKind};
     use tableau::initials::get_initial_table_from;
-    use tableau::enter_vars::{enter_var_pivot_optimal, enter_var_pivot_feasible};
-    use tableau::leave_vars::leave_var;
+    use tableau::enter_vars::{enter_var_pivot_optimal, enter_var_dual, enter_var_bland,
+                              enter_var_steepest_edge, enter_var_weighted, enter_var_partial};
+    use tableau::leave_vars::{Step, leave_var, leave_var_dual, get_unbounded_ray, leave_var_bland,
+                              leave_var_lexicographic, leave_var_bounded};
     use tableau::pivots::pivot_around;
+    use tableau::ranges::{get_cost_delta_range, get_rhs_delta_range};
+    use tableau::tolerances::Tolerances;
+    use tableau::pivot_rules::{PivotRule, Devex};
+    use errors::SolveError;
 
     #[test]
     fn can_create_tables() {
@@ -29,11 +38,11 @@ mod tests {
         let table = Table::new(column_names, rows);
         let table_header = table.get_column_names();
         let table_rows = table.get_rows();
-        assert!(table_header.contains_key("x"));
-        assert!(table_header.contains_key("y"));
+        assert!(table_header.contains_key(&Name::from("x")));
+        assert!(table_header.contains_key(&Name::from("y")));
         assert_eq!(2, table_header.len());
-        assert_eq!(0, *table_header.get("x").unwrap());
-        assert_eq!(1, *table_header.get("y").unwrap());
+        assert_eq!(0, *table_header.get(&Name::from("x")).unwrap());
+        assert_eq!(1, *table_header.get(&Name::from("y")).unwrap());
         assert_eq!(1.0, table_rows[0][0]);
         assert_eq!(2.0, table_rows[0][1]);
         assert_eq!(3.0, table_rows[1][0]);
@@ -52,11 +61,11 @@ mod tests {
         table.sub_cell(1, 1, 2.0);
         let table_header = table.get_column_names();
         let table_rows = table.get_rows();
-        assert!(table_header.contains_key("x"));
-        assert!(table_header.contains_key("y"));
+        assert!(table_header.contains_key(&Name::from("x")));
+        assert!(table_header.contains_key(&Name::from("y")));
         assert_eq!(2, table_header.len());
-        assert_eq!(0, *table_header.get("x").unwrap());
-        assert_eq!(1, *table_header.get("y").unwrap());
+        assert_eq!(0, *table_header.get(&Name::from("x")).unwrap());
+        assert_eq!(1, *table_header.get(&Name::from("y")).unwrap());
         assert_eq!(1.0, table_rows[0][0]);
         assert_eq!(2.0, table_rows[0][1]);
         assert_eq!(3.0, table_rows[1][0]);
@@ -75,11 +84,11 @@ mod tests {
         table.div_cell(2, 1, 2.0);
         let table_header = table.get_column_names();
         let table_rows = table.get_rows();
-        assert!(table_header.contains_key("x"));
-        assert!(table_header.contains_key("y"));
+        assert!(table_header.contains_key(&Name::from("x")));
+        assert!(table_header.contains_key(&Name::from("y")));
         assert_eq!(2, table_header.len());
-        assert_eq!(0, *table_header.get("x").unwrap());
-        assert_eq!(1, *table_header.get("y").unwrap());
+        assert_eq!(0, *table_header.get(&Name::from("x")).unwrap());
+        assert_eq!(1, *table_header.get(&Name::from("y")).unwrap());
         assert_eq!(1.0, table_rows[0][0]);
         assert_eq!(2.0, table_rows[0][1]);
         assert_eq!(3.0, table_rows[1][0]);
@@ -103,7 +112,7 @@ mod tests {
                                vec![0.0, 0.0, 0.0, 1.0 / 3.0, 1.0, -3.0, 5.0],
                                vec![0.0, 1.0, 0.0, -1.0 / 3.0, 0.0, 0.5, 10.0]];
         let table1 = Table::new(column_names1, table1_rows);
-        assert_eq!(vec![("x".to_string(), 10.0), ("y".to_string(), 10.0), ("t".to_string(), 5.0)],
+        assert_eq!(vec![(Name::from("x"), 10.0), (Name::from("y"), 10.0), (Name::from("t"), 5.0)],
                    table1.get_basic_solution().unwrap());
 
         let mut column_names2: HashMap<String, usize> = HashMap::new();
@@ -120,6 +129,15 @@ mod tests {
                                vec![0.0, 1.0, 0.0, -1.0 / 3.0, 0.0, 0.5, 10.0]];
         let table2 = Table::new(column_names2, table2_rows);
         assert_eq!((1, 2), table2.get_basic_solution().err().unwrap());
+
+        // A -1.0 column in a row that already has a basic variable is not basic itself.
+        let mut column_names3: HashMap<String, usize> = HashMap::new();
+        column_names3.insert("x".to_string(), 0);
+        column_names3.insert("s".to_string(), 1);
+        column_names3.insert("RHS".to_string(), 2);
+        let table3_rows = vec![vec![1.0, -1.0, 10.0], vec![0.0, 0.0, 0.0]];
+        let table3 = Table::new(column_names3, table3_rows);
+        assert_eq!(vec![(Name::from("x"), 10.0)], table3.get_basic_solution().unwrap());
     }
 
     #[test]
@@ -137,7 +155,7 @@ mod tests {
                                vec![0.0, 0.0, 0.0, 1.0 / 3.0, 1.0, -3.0, 5.0],
                                vec![0.0, 1.0, 0.0, -1.0 / 3.0, 0.0, 0.5, 10.0]];
         let table1 = Table::new(column_names1, table1_rows);
-        assert_eq!(false, table1.is_solution_optimal());
+        assert_eq!(false, table1.is_solution_optimal().unwrap());
 
         let mut column_names2: HashMap<String, usize> = HashMap::new();
         column_names2.insert("P".to_string(), 0);
@@ -152,7 +170,7 @@ mod tests {
                                vec![0.0, 0.0, 0.0, 1.0 / 3.0, 1.0, -3.0, 5.0],
                                vec![0.0, 1.0, 0.0, 1.0 / 3.0, 0.0, 0.5, 10.0]];
         let table2 = Table::new(column_names2, table2_rows);
-        assert_eq!(true, table2.is_solution_optimal());
+        assert_eq!(true, table2.is_solution_optimal().unwrap());
 
         let mut column_names3: HashMap<String, usize> = HashMap::new();
         column_names3.insert("P".to_string(), 0);
@@ -167,7 +185,121 @@ mod tests {
                                vec![0.0, 0.0, 0.0, 1.0 / 3.0, 1.0, -3.0, 5.0],
                                vec![0.0, 1.0, 0.0, 1.0 / 3.0, 0.0, 0.5, -10.0]];
         let table3 = Table::new(column_names3, table3_rows);
-        assert_eq!(true, table3.is_solution_optimal());
+        assert_eq!(true, table3.is_solution_optimal().unwrap());
+    }
+
+    #[test]
+    fn can_get_row_of_basic_var() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("s".to_string(), 2);
+        column_names.insert("RHS".to_string(), 3);
+        let table_rows = vec![vec![0.0, 1.0, 0.5, 10.0],
+                              vec![1.0, 0.0, -0.5, 5.0],
+                              vec![0.0, 0.0, 2.0, 55.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!(Ok(1), table.get_row_of_basic_var("x"));
+        assert_eq!(Ok(0), table.get_row_of_basic_var("y"));
+        match table.get_row_of_basic_var("s") {
+            Err(SolveError::MalformedModel(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        match table.get_row_of_basic_var("z") {
+            Err(SolveError::MalformedModel(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(Some(1), table.get_basic_var_of_row(0));
+        assert_eq!(Some(0), table.get_basic_var_of_row(1));
+        assert_eq!(None, table.get_basic_var_of_row(2));
+    }
+
+    #[test]
+    fn can_compare_within_tolerances() {
+        let tolerances = Tolerances::new(1e-6, 1e-3);
+        assert!(tolerances.is_zero(&-1e-7));
+        assert!(!tolerances.is_zero(&1e-5));
+        assert!(tolerances.is_one(&0.99999994));
+        assert!(!tolerances.is_one(&0.999));
+        assert!(tolerances.is_positive(&1e-5));
+        assert!(!tolerances.is_positive(&1e-7));
+        assert!(tolerances.is_negative(&-1e-5));
+        assert!(!tolerances.is_negative(&-1e-7));
+        assert!(tolerances.is_zero_cost(&-1e-4));
+        assert!(!tolerances.is_improving_cost(&-1e-4));
+        assert!(tolerances.is_improving_cost(&-1e-2));
+        assert_eq!(Tolerances::new(1e-9, 1e-9), Tolerances::<f64>::default());
+    }
+
+    #[test]
+    fn can_get_basic_vars_off_by_rounding() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("s".to_string(), 2);
+        column_names.insert("RHS".to_string(), 3);
+        let table_rows = vec![vec![1e-8f32, 0.99999994, 0.5, 10.0],
+                              vec![1.0000001, 0.0, -0.5, 5.0],
+                              vec![0.0, -1e-8, 2.0, 55.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!(Ok(1), table.get_row_of_basic_var("x"));
+        assert_eq!(Ok(0), table.get_row_of_basic_var("y"));
+        assert_eq!(Ok(vec![(Name::from("x"), 5.0), (Name::from("y"), 10.0)]),
+                   table.get_basic_solution());
+    }
+
+    #[test]
+    fn can_track_basis() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("RHS".to_string(), 4);
+        // y looks just like s1 but only one of them can be basic in the first row.
+        let table_rows = vec![vec![1.0, 1.0, 1.0, 0.0, 4.0],
+                              vec![2.0, 0.0, 0.0, 1.0, 6.0],
+                              vec![-3.0, 0.0, 0.0, 0.0, 0.0]];
+        let mut table = Table::new(column_names, table_rows);
+        assert_eq!(vec![Some(1), Some(3), None], *table.get_basis());
+        assert!(!table.is_basic(2));
+        assert_eq!(Ok(vec![(Name::from("y"), 4.0), (Name::from("s2"), 6.0)]),
+                   table.get_basic_solution());
+        pivot_around(0, 1, &mut table);
+        assert_eq!(vec![Some(1), Some(0), None], *table.get_basis());
+        assert_eq!(Ok(1), table.get_row_of_basic_var("x"));
+        assert!(table.get_row_of_basic_var("s2").is_err());
+        assert_eq!(Some(1), table.remove_column("y"));
+        assert_eq!(vec![None, Some(0), None], *table.get_basis());
+        table.remove_row(0);
+        assert_eq!(vec![Some(0), None], *table.get_basis());
+        table.set_basic_var_of_row(1, 2);
+        assert_eq!(Some(2), table.get_basic_var_of_row(1));
+    }
+
+    #[test]
+    fn can_insert_and_remove_rows_and_columns() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("RHS".to_string(), 2);
+        let table_rows = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let mut table = Table::new(column_names, table_rows);
+        table.set_column_kind(0, ColumnKind::Internal);
+        table.insert_row(1, vec![7.0, 8.0, 9.0]);
+        assert_eq!(vec![7.0, 8.0, 9.0], table.get_rows()[1]);
+        assert_eq!(3, table.get_rows().len());
+        assert_eq!(Some(0), table.remove_column("x"));
+        assert_eq!(None, table.remove_column("x"));
+        assert_eq!(0, *table.get_column_names().get(&Name::from("y")).unwrap());
+        assert_eq!(1, *table.get_column_names().get(&Name::from("RHS")).unwrap());
+        assert_eq!(ColumnKind::Decision, table.get_column_kind(0));
+        table.append_empty_column(Name::from("arti1"));
+        table.set_column_kind(1, ColumnKind::Artificial);
+        assert_eq!(vec![1], table.get_excluded_columns());
+        table.remove_column("arti1");
+        assert_eq!(vec![8.0, 9.0], table.remove_row(1));
+        assert_eq!(vec![vec![2.0, 3.0], vec![5.0, 6.0]], *table.get_rows());
     }
 
     #[test]
@@ -196,20 +328,22 @@ mod tests {
         let table_rows = table.get_rows();
         assert_eq!(7, table_header.len());
         assert_eq!(3, table_rows.len());
-        assert!(table_header.contains_key("x1"));
-        assert!(table_header.contains_key("x2"));
-        assert!(table_header.contains_key("x3"));
-        assert!(table_header.contains_key("sl1"));
-        assert!(table_header.contains_key("sl2"));
-        assert!(table_header.contains_key("Z"));
-        assert!(table_header.contains_key("RHS"));
-        assert_eq!(0, *table_header.get("x1").unwrap());
-        assert_eq!(1, *table_header.get("x2").unwrap());
-        assert_eq!(2, *table_header.get("x3").unwrap());
-        assert_eq!(3, *table_header.get("sl1").unwrap());
-        assert_eq!(4, *table_header.get("sl2").unwrap());
-        assert_eq!(5, *table_header.get("Z").unwrap());
-        assert_eq!(6, *table_header.get("RHS").unwrap());
+        assert!(table_header.contains_key(&Name::from("x1")));
+        assert!(table_header.contains_key(&Name::from("x2")));
+        assert!(table_header.contains_key(&Name::from("x3")));
+        assert!(table_header.contains_key(&Name::from("sl1")));
+        assert!(table_header.contains_key(&Name::from("sl2")));
+        assert!(table_header.contains_key(&Name::from("Z")));
+        assert!(table_header.contains_key(&Name::from("RHS")));
+        assert_eq!(0, *table_header.get(&Name::from("x1")).unwrap());
+        assert_eq!(1, *table_header.get(&Name::from("x2")).unwrap());
+        assert_eq!(2, *table_header.get(&Name::from("x3")).unwrap());
+        assert_eq!(3, *table_header.get(&Name::from("sl1")).unwrap());
+        assert_eq!(4, *table_header.get(&Name::from("sl2")).unwrap());
+        assert_eq!(5, *table_header.get(&Name::from("Z")).unwrap());
+        assert_eq!(6, *table_header.get(&Name::from("RHS")).unwrap());
+        assert_eq!(ColumnKind::Decision, table.get_column_kind(2));
+        assert_eq!(ColumnKind::Internal, table.get_column_kind(3));
         assert_eq!(vec![0.5, 2.0, 1.0, 1.0, 0.0, 0.0, 24.0], table_rows[0]);
         assert_eq!(vec![1.0, 2.0, 4.0, 0.0, 1.0, 0.0, 60.0], table_rows[1]);
         assert_eq!(vec![-6.0, -14.0, -13.0, 0.0, 0.0, 1.0, 0.0], table_rows[2]);
@@ -228,38 +362,55 @@ mod tests {
                               vec![1.0, 2.0, 4.0, 0.0, 1.0, 60.0],
                               vec![-6.0, -14.0, -13.0, 0.0, 0.0, 0.0]];
         let table = Table::new(column_names, table_rows);
-        assert_eq!(1, enter_var_pivot_optimal(&table));
+        assert_eq!(Some(1), enter_var_pivot_optimal(&table));
+
+        // Artificial columns never enter, so with x basic there is nothing left to choose.
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("arti1".to_string(), 1);
+        column_names.insert("RHS".to_string(), 2);
+        let table_rows = vec![vec![1.0, 1.0, 5.0], vec![0.0, -1.0, 0.0]];
+        let mut table = Table::new(column_names, table_rows);
+        table.set_basic_var_of_row(0, 0);
+        table.set_column_kind(1, ColumnKind::Artificial);
+        assert_eq!(None, enter_var_pivot_optimal(&table));
     }
 
     #[test]
-    fn can_enter_var_pivot_feasible() {
-        let mut column_names1: HashMap<String, usize> = HashMap::new();
-        column_names1.insert("x1".to_string(), 0);
-        column_names1.insert("x2".to_string(), 1);
-        column_names1.insert("x3".to_string(), 2);
-        column_names1.insert("s1".to_string(), 3);
-        column_names1.insert("s2".to_string(), 4);
-        column_names1.insert("RHS".to_string(), 5);
-        column_names1.insert("Value".to_string(), 6);
-        let table1_rows = vec![vec![0.5, 2.0, 1.0, 1.0, 0.0, 24.0],
-                               vec![-1.0, 0.0, 4.0, 0.0, 1.0, 60.0],
-                               vec![-14.0, -6.0, -13.0, 0.0, 0.0, 0.0]];
-        let table1 = Table::new(column_names1, table1_rows);
-        assert_eq!(2, enter_var_pivot_feasible(&table1, 1, 4).unwrap());
+    fn can_pivot_dual() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("RHS".to_string(), 4);
+        let table_rows = vec![vec![1.0, 0.0, -1.0, -0.5, -4.0],
+                              vec![0.0, 1.0, 1.0, -1.0, -6.0],
+                              vec![0.0, 0.0, 3.0, 2.0, 10.0]];
+        let mut table = Table::new(column_names, table_rows);
+        assert_eq!(Some(1), leave_var_dual(&table));
+        assert_eq!(Some(3), enter_var_dual(1, &table));
+        assert_eq!(Some(2), enter_var_dual(0, &table));
+        pivot_around(3, 1, &mut table);
+        assert_eq!(vec![1.0, -0.5, -1.5, 0.0, -1.0], table.get_rows()[0]);
+        assert_eq!(vec![0.0, -1.0, -1.0, 1.0, 6.0], table.get_rows()[1]);
+        assert_eq!(Some(0), leave_var_dual(&table));
+    }
 
-        let mut column_names2: HashMap<String, usize> = HashMap::new();
-        column_names2.insert("x1".to_string(), 0);
-        column_names2.insert("x2".to_string(), 1);
-        column_names2.insert("x3".to_string(), 2);
-        column_names2.insert("s1".to_string(), 3);
-        column_names2.insert("s2".to_string(), 4);
-        column_names2.insert("RHS".to_string(), 5);
-        column_names2.insert("Value".to_string(), 6);
-        let table2_rows = vec![vec![0.5, 2.0, 1.0, 1.0, 0.0, 24.0],
-                               vec![-1.0, 0.0, -4.0, 0.0, 1.0, 60.0],
-                               vec![-14.0, -6.0, -13.0, 0.0, 0.0, 0.0]];
-        let table2 = Table::new(column_names2, table2_rows);
-        assert_eq!(true, enter_var_pivot_feasible(&table2, 1, 4).is_none());
+    #[test]
+    fn can_get_delta_ranges() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x".to_string(), 0);
+        column_names.insert("y".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("P".to_string(), 3);
+        column_names.insert("RHS".to_string(), 4);
+        let table_rows = vec![vec![1.0, 1.0, 1.0, 0.0, 4.0], vec![0.0, 2.0, 3.0, 1.0, 12.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!((Some(-2.0), None), get_cost_delta_range(0, Some(0), &[1, 2], &table));
+        assert_eq!((None, Some(2.0)), get_cost_delta_range(1, None, &[1, 2], &table));
+        assert_eq!((Some(-4.0), None), get_rhs_delta_range(2, 1.0, &table));
+        assert_eq!((None, Some(4.0)), get_rhs_delta_range(2, -1.0, &table));
     }
 
     #[test]
@@ -275,8 +426,8 @@ mod tests {
                                vec![1.0, 2.0, 4.0, 0.0, 1.0, 60.0],
                                vec![-14.0, -6.0, -13.0, 0.0, 0.0, 0.0]];
         let table1 = Table::new(column_names1, table1_rows);
-        let enter_var_index1 = enter_var_pivot_optimal(&table1);
-        assert_eq!(0, leave_var(enter_var_index1, &table1));
+        let enter_var_index1 = enter_var_pivot_optimal(&table1).unwrap();
+        assert_eq!(Some(0), leave_var(enter_var_index1, &table1));
 
         let mut column_names2: HashMap<String, usize> = HashMap::new();
         column_names2.insert("x1".to_string(), 0);
@@ -289,8 +440,8 @@ mod tests {
                                vec![1.0, 2.0, 4.0, 0.0, 1.0, 60.0],
                                vec![-14.0, -6.0, -13.0, 0.0, 0.0, 0.0]];
         let table2 = Table::new(column_names2, table2_rows);
-        let enter_var_index2 = enter_var_pivot_optimal(&table2);
-        assert_eq!(1, leave_var(enter_var_index2, &table2));
+        let enter_var_index2 = enter_var_pivot_optimal(&table2).unwrap();
+        assert_eq!(Some(1), leave_var(enter_var_index2, &table2));
 
         let mut column_names3: HashMap<String, usize> = HashMap::new();
         column_names3.insert("x1".to_string(), 0);
@@ -305,8 +456,155 @@ mod tests {
                                vec![1.0, 2.0, 4.0, 0.0, 1.0, 47.0],
                                vec![-14.0, -6.0, -13.0, 0.0, 0.0, 0.0]];
         let table3 = Table::new(column_names3, table3_rows);
-        let enter_var_index3 = enter_var_pivot_optimal(&table3);
-        assert_eq!(3, leave_var(enter_var_index3, &table3));
+        let enter_var_index3 = enter_var_pivot_optimal(&table3).unwrap();
+        assert_eq!(Some(3), leave_var(enter_var_index3, &table3));
+
+        let mut column_names4: HashMap<String, usize> = HashMap::new();
+        column_names4.insert("x1".to_string(), 0);
+        column_names4.insert("x2".to_string(), 1);
+        column_names4.insert("s1".to_string(), 2);
+        column_names4.insert("s2".to_string(), 3);
+        column_names4.insert("RHS".to_string(), 4);
+        let table4_rows = vec![vec![-1.0, 1.0, 1.0, 0.0, 4.0],
+                               vec![0.0, 1.0, 0.0, 1.0, 6.0],
+                               vec![-3.0, -1.0, 0.0, 0.0, 0.0]];
+        let table4 = Table::new(column_names4, table4_rows);
+        let enter_var_index4 = enter_var_pivot_optimal(&table4).unwrap();
+        assert_eq!(None, leave_var(enter_var_index4, &table4));
+
+        // A surplus basic with -1 at zero blocks x the moment it would have to go negative.
+        let mut column_names5: HashMap<String, usize> = HashMap::new();
+        column_names5.insert("x".to_string(), 0);
+        column_names5.insert("su1".to_string(), 1);
+        column_names5.insert("RHS".to_string(), 2);
+        let table5_rows = vec![vec![-1.0, -1.0, 0.0], vec![-1.0, 0.0, 0.0]];
+        let mut table5 = Table::new(column_names5, table5_rows);
+        table5.set_basic_var_of_row(0, 1);
+        assert_eq!(Some(0), leave_var(0, &table5));
+    }
+
+    #[test]
+    fn can_break_ties_by_pivot_rule() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x1".to_string(), 0);
+        column_names.insert("x2".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("RHS".to_string(), 4);
+        let table_rows = vec![vec![1.0, 1.0, 0.0, 1.0, 0.0],
+                              vec![2.0, 1.0, 1.0, 0.0, 0.0],
+                              vec![-1.0, -2.0, 0.0, 0.0, 0.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!(Some(1), enter_var_pivot_optimal(&table));
+        assert_eq!(Some(0), enter_var_bland(&table));
+        // Both rows block x1 at zero.
+        assert_eq!(Some(0), leave_var(0, &table));
+        assert_eq!(Some(1), leave_var_bland(0, &table));
+        assert_eq!(Some(1), leave_var_lexicographic(0, &table));
+    }
+
+    #[test]
+    fn can_price_by_edge_weights() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x1".to_string(), 0);
+        column_names.insert("x2".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("RHS".to_string(), 4);
+        let table_rows = vec![vec![4.0, 0.0, 1.0, 0.0, 8.0],
+                              vec![0.0, 1.0, 0.0, 1.0, 4.0],
+                              vec![-2.0, -1.5, 0.0, 0.0, 0.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!(Some(0), enter_var_weighted(&table, &[1.0, 1.0, 1.0, 1.0]));
+        // x1 has the larger reduced cost but a much longer edge.
+        assert_eq!(Some(1), enter_var_steepest_edge(&table));
+        assert_eq!(Some((1, 2)), enter_var_partial(&table, 1, 1));
+        assert_eq!(Some((0, 1)), enter_var_partial(&table, 2, 1));
+        assert_eq!(Some((0, 2)), enter_var_partial(&table, 0, 2));
+    }
+
+    #[test]
+    fn can_update_devex_weights_on_pivots() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x1".to_string(), 0);
+        column_names.insert("x2".to_string(), 1);
+        column_names.insert("x3".to_string(), 2);
+        column_names.insert("s1".to_string(), 3);
+        column_names.insert("s2".to_string(), 4);
+        column_names.insert("RHS".to_string(), 5);
+        let table_rows = vec![vec![1.0, -2.0, -0.5, 1.0, 0.0, 8.0],
+                              vec![0.0, 0.0, 1.0, 0.0, 1.0, 8.0],
+                              vec![-4.0, -1.0, -3.9, 0.0, 0.0, 0.0]];
+        let mut table = Table::new(column_names, table_rows);
+        let mut devex = Devex::new();
+        let mut unaware_devex = Devex::new();
+        assert_eq!(Some(0), devex.enter_var(&table));
+        assert_eq!(Some(0), unaware_devex.enter_var(&table));
+        assert_eq!(Some(0), devex.leave_var(0, &table));
+        assert_eq!(Some(0), unaware_devex.leave_var(0, &table));
+        pivot_around(0, 0, &mut table);
+        devex.pivoted(&table, 0, 0);
+        // x2 now has the most negative reduced cost but moved 2 units for every unit of x1, so
+        // its edge is too long to beat x3 once the weights know about the pivot.
+        assert_eq!(Some(1), enter_var_pivot_optimal(&table));
+        assert_eq!(Some(2), devex.enter_var(&table));
+        // Choosing the leaving row alone does not count as a pivot.
+        assert_eq!(Some(1), unaware_devex.enter_var(&table));
+    }
+
+    #[test]
+    fn can_get_unbounded_ray() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x1".to_string(), 0);
+        column_names.insert("x2".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("P".to_string(), 4);
+        column_names.insert("RHS".to_string(), 5);
+        let table_rows = vec![vec![-1.0, 1.0, 1.0, 0.0, 0.0, 4.0],
+                              vec![-2.0, 0.0, 0.0, 1.0, 0.0, 6.0],
+                              vec![-3.0, -1.0, 0.0, 0.0, 1.0, 0.0]];
+        let table = Table::new(column_names, table_rows);
+        assert_eq!(vec![(Name::from("x1"), 1.0), (Name::from("s1"), 1.0), (Name::from("s2"), 2.0)],
+                   get_unbounded_ray(0, &table));
+    }
+
+    #[test]
+    fn can_bound_columns() {
+        let mut column_names: HashMap<String, usize> = HashMap::new();
+        column_names.insert("x1".to_string(), 0);
+        column_names.insert("x2".to_string(), 1);
+        column_names.insert("s1".to_string(), 2);
+        column_names.insert("s2".to_string(), 3);
+        column_names.insert("P".to_string(), 4);
+        column_names.insert("RHS".to_string(), 5);
+        let table_rows = vec![vec![1.0, 1.0, 1.0, 0.0, 0.0, 4.0],
+                              vec![1.0, -1.0, 0.0, 1.0, 0.0, 2.0],
+                              vec![-3.0, -2.0, 0.0, 0.0, 1.0, 0.0]];
+        let mut table = Table::new(column_names, table_rows);
+        table.set_bound(0, 1.0, Some(2.0));
+        table.set_bound(1, 0.0, Some(3.0));
+        assert_eq!(Some(1.0), table.get_width(0));
+        assert_eq!(None, table.get_width(2));
+        // x1 reaches its upper bound before either slack runs out.
+        assert_eq!(Some(Step::Flip), leave_var_bounded(0, leave_var(0, &table), &table));
+        table.complement_column(0);
+        assert!(table.is_complemented(0));
+        assert_eq!(vec![-1.0, 1.0, 1.0, 0.0, 0.0, 3.0], table.get_rows()[0]);
+        assert_eq!(vec![3.0, -2.0, 0.0, 0.0, 1.0, 3.0], table.get_rows()[2]);
+        assert_eq!(2.0, table.get_value_of(0, 0.0));
+        // x2 grows the basic s2 of the second row, which has no upper bound to hit.
+        assert_eq!(Some(Step::Pivot(0)), leave_var_bounded(1, leave_var(1, &table), &table));
+        pivot_around(1, 0, &mut table);
+        assert_eq!(vec![(Name::from("x1"), -1.0), (Name::from("x2"), 1.0)],
+                   get_unbounded_ray(0, &table)
+                       .into_iter()
+                       .take(2)
+                       .collect::<Vec<(Name, f64)>>());
+        // Complementing the basic x2 keeps a unit entry in its row.
+        table.complement_column(1);
+        assert_eq!(vec![1.0, 1.0, -1.0, 0.0, 0.0, 0.0], table.get_rows()[0]);
+        assert_eq!(3.0, table.get_value_of(1, 0.0));
     }
 
     #[test]
@@ -0,0 +1,206 @@
+use std::fmt;
+use tableau::tables::Table;
+use tableau::enter_vars::{enter_var_pivot_optimal, enter_var_bland, enter_var_steepest_edge,
+                          enter_var_weighted, enter_var_partial};
+use tableau::leave_vars::{leave_var, leave_var_bland, leave_var_lexicographic};
+use Num;
+
+/// Chooses the variable that enters the basis and the one that leaves it on every pivot.
+pub trait PivotRule<N>: fmt::Debug {
+    /// The column to bring into the basis of a table that is not optimal yet.
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize>;
+
+    /// The row whose basic variable makes way for the entering column, `None` if no row limits
+    /// how far it can grow.
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize>;
+
+    /// Called after every pivot of the solve with the table as it now is, the row pivoted on
+    /// and the column that entered, whichever rule chose them.
+    fn pivoted(&mut self, _table: &Table<N>, _row: usize, _column: usize) {}
+
+    /// A fresh copy of the rule for another solve.
+    fn box_clone(&self) -> Box<dyn PivotRule<N>>;
+}
+
+impl<N> Clone for Box<dyn PivotRule<N>> {
+    fn clone(&self) -> Box<dyn PivotRule<N>> {
+        self.box_clone()
+    }
+}
+
+/// Most negative reduced cost in, first row with the smallest ratio out. Usually takes few
+/// pivots but may cycle on degenerate tables.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dantzig;
+
+impl<N: Num> PivotRule<N> for Dantzig {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        enter_var_pivot_optimal(table)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var(enter_var_index, table)
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(*self)
+    }
+}
+
+/// First improving column in, ties out broken by the first basic variable. Never cycles but
+/// tends to take more pivots.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bland;
+
+impl<N: Num> PivotRule<N> for Bland {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        enter_var_bland(table)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var_bland(enter_var_index, table)
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(*self)
+    }
+}
+
+/// Dantzig's choice of entering column with ties out broken lexicographically, which keeps
+/// degenerate pivots from revisiting a basis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lexicographic;
+
+impl<N: Num> PivotRule<N> for Lexicographic {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        enter_var_pivot_optimal(table)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var_lexicographic(enter_var_index, table)
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(*self)
+    }
+}
+
+/// Brings in the column whose edge climbs the steepest, measured exactly from the table. Takes
+/// the fewest pivots of these rules but each one costs a pass over the whole table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SteepestEdge;
+
+impl<N: Num> PivotRule<N> for SteepestEdge {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        enter_var_steepest_edge(table)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var(enter_var_index, table)
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(*self)
+    }
+}
+
+/// Approximates steepest edge with reference weights that are cheap to keep up to date from
+/// the pivot row alone.
+#[derive(Debug, Clone)]
+pub struct Devex<N> {
+    weights: Vec<N>,
+}
+
+impl<N: Num> Devex<N> {
+    pub fn new() -> Devex<N> {
+        Devex { weights: Vec::new() }
+    }
+
+    fn update_weights(&mut self, table: &Table<N>, row: usize, enter_var_index: usize) {
+        // The pivot row now holds every entry divided by the pivot value, which is 1 / pivot
+        // value under the column that left.
+        let pivot_row = &table.get_rows()[row];
+        let enter_weight = self.weights[enter_var_index].clone();
+        // Every other column moves along with the entering one in proportion to its entry in
+        // the pivot row, the leaving one taking over the weight of the entering one...
+        for (column, weight) in self.weights.iter_mut().enumerate() {
+            if column == enter_var_index || table.is_basic(column) {
+                continue;
+            }
+            let candidate = pivot_row[column].clone() * pivot_row[column].clone() *
+                            enter_weight.clone();
+            if candidate > *weight {
+                *weight = candidate;
+            }
+        }
+        // ... and a basic column starts over from a unit weight for when it leaves again.
+        self.weights[enter_var_index] = N::one();
+    }
+}
+
+impl<N: Num> Default for Devex<N> {
+    fn default() -> Devex<N> {
+        Devex::new()
+    }
+}
+
+impl<N: Num + 'static> PivotRule<N> for Devex<N> {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        // Start from a reference framework of unit weights for the table at hand.
+        let num_columns = table.get_column_names().len() - 1;
+        if self.weights.len() != num_columns {
+            self.weights = vec![N::one(); num_columns];
+        }
+        enter_var_weighted(table, &self.weights)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var(enter_var_index, table)
+    }
+
+    fn pivoted(&mut self, table: &Table<N>, row: usize, column: usize) {
+        // Weights for a table with other columns are reset on the next pick anyway.
+        if self.weights.len() == table.get_column_names().len() - 1 {
+            self.update_weights(table, row, column);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(Devex::new())
+    }
+}
+
+/// Prices a segment of columns at a time and only moves on to the next segment once the
+/// current one has nothing left to improve, so each pivot looks at fewer columns.
+#[derive(Debug, Clone, Copy)]
+pub struct PartialPricing {
+    segment_size: usize,
+    start: usize,
+}
+
+impl PartialPricing {
+    pub fn new(segment_size: usize) -> PartialPricing {
+        PartialPricing {
+            segment_size,
+            start: 0,
+        }
+    }
+}
+
+impl<N: Num> PivotRule<N> for PartialPricing {
+    fn enter_var(&mut self, table: &Table<N>) -> Option<usize> {
+        let (enter_var_index, next_start) = enter_var_partial(table,
+                                                              self.start,
+                                                              self.segment_size)?;
+        self.start = next_start;
+        Some(enter_var_index)
+    }
+
+    fn leave_var(&mut self, enter_var_index: usize, table: &Table<N>) -> Option<usize> {
+        leave_var(enter_var_index, table)
+    }
+
+    fn box_clone(&self) -> Box<dyn PivotRule<N>> {
+        Box::new(PartialPricing::new(self.segment_size))
+    }
+}
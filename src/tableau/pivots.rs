@@ -1,65 +1,71 @@
-use math::variables::AbstVar;
+use math::variables::{AbstVar, Name};
 use objective::constraints::{Constraint, SystemOfConstraints};
 use tableau::tables::Table;
+use errors::SolveError;
 use Num;
 
-pub fn pivot_around(enter_var_index: usize, leave_var_index: usize, table: &mut Table) {
+pub fn pivot_around<N: Num>(enter_var_index: usize, leave_var_index: usize, table: &mut Table<N>) {
     let row_len = table.get_rows()[leave_var_index].len();
-    let pivot_value = table.get_rows()[leave_var_index][enter_var_index];
+    let pivot_value = table.get_rows()[leave_var_index][enter_var_index].clone();
     // Add multiples of pivot row to other rows to make their pivot column
     // entry 0.0.
     for row_index in 0..table.get_rows().len() {
         if row_index != leave_var_index {
-            let value_corres_pivot_column = table.get_rows()[row_index][enter_var_index];
+            let value_corres_pivot_column = table.get_rows()[row_index][enter_var_index].clone();
+            // Rows with nothing in the pivot column stay as they are.
+            if table.get_tolerances().is_zero(&value_corres_pivot_column) {
+                continue;
+            }
             for i in 0..row_len {
                 // value in line of pivot corresponding column of current cell *
                 // value in current line corresponding pivot cell column
-                let numerator = table.get_rows()[leave_var_index][i] * value_corres_pivot_column;
-                table.sub_cell(row_index, i, numerator / pivot_value);
+                let numerator = table.get_rows()[leave_var_index][i].clone() *
+                                value_corres_pivot_column.clone();
+                table.sub_cell(row_index, i, numerator / pivot_value.clone());
             }
         }
     }
     // Scale pivot row such that the pivot cell becomes 1.0.
-    if pivot_value != 1.0 {
+    if pivot_value != N::one() {
         for i in 0..row_len {
-            table.div_cell(leave_var_index, i, pivot_value);
+            table.div_cell(leave_var_index, i, pivot_value.clone());
         }
     }
+    table.set_basic_var_of_row(leave_var_index, enter_var_index);
 }
 
-pub fn apply_transition_rule(a_v_i_s: Vec<(String, Num)>,
-                             s_c: &SystemOfConstraints,
-                             table: &mut Table) {
+pub fn apply_transition_rule<N: Num>(a_v_i_s: Vec<(Name, N)>,
+                                     s_c: &SystemOfConstraints<N>,
+                                     table: &mut Table<N>)
+                                     -> Result<(), SolveError<N>> {
     'next_arti_var: for basic_arti_var in a_v_i_s.iter() {
-        let regular_constraints = s_c.system()
-            .iter()
-            .filter(|constraint| match constraint {
-                        &&Constraint::Regular(_) => true,
-                        _ => false,
-                    })
-            .collect::<Vec<&Constraint>>();
-        let arti_var_row = table.get_row_of_basic_var(&basic_arti_var.0);
-        for constraint in regular_constraints {
-            match constraint {
-                &Constraint::Regular(ref exp) => {
-                    for var in exp.lhs() {
-                        // Pivot on non artificial variable with coefficient not 0.
-                        match var {
-                            &AbstVar::ArtiVar { .. } => continue,
-                            non_arti_var => {
-                                let non_arti_var_column = *table.get_column_names()
-                                    .get(non_arti_var.name())
-                                    .expect("Failed to get row number for non-artificial varible.");
-                                if table.get_rows()[arti_var_row][non_arti_var_column] != 0.0 {
-                                    pivot_around(non_arti_var_column, arti_var_row, table);
-                                    continue 'next_arti_var;
+        let arti_var_row = table.get_row_of_basic_var(&basic_arti_var.0)?;
+        for constraint in s_c.system() {
+            if let Constraint::Regular(ref exp) | Constraint::Weighted(ref exp, _) = *constraint {
+                for var in exp.lhs() {
+                    // Pivot on non artificial variable with coefficient not 0.
+                    match *var {
+                        AbstVar::ArtiVar { .. } => continue,
+                        ref non_arti_var => {
+                            let name = non_arti_var.name();
+                            let non_arti_var_column = match table.get_column_names().get(name) {
+                                Some(column) => *column,
+                                None => {
+                                    let reason = format!("\"{}\" is not a column of the table.",
+                                                         name);
+                                    return Err(SolveError::MalformedModel(reason));
                                 }
+                            };
+                            let cell = &table.get_rows()[arti_var_row][non_arti_var_column];
+                            if !table.get_tolerances().is_zero(cell) {
+                                pivot_around(non_arti_var_column, arti_var_row, table);
+                                continue 'next_arti_var;
                             }
                         }
                     }
                 }
-                _ => panic!("Only expected Regular constraints in this iterator."),
             }
         }
     }
+    Ok(())
 }
@@ -0,0 +1,75 @@
+use tableau::tables::Table;
+use Num;
+
+// Bounds are `None` where nothing limits the change.
+pub fn get_cost_delta_range<N: Num>(column: usize,
+                                    basic_row: Option<usize>,
+                                    non_basic_columns: &[usize],
+                                    table: &Table<N>)
+                                    -> (Option<N>, Option<N>) {
+    let table_rows = table.get_rows();
+    let function_row = &table_rows[table_rows.len() - 1];
+    match basic_row {
+        // A non-basic variable enters once its coefficient has gained its reduced cost.
+        None => (None, Some(function_row[column].clone())),
+        // Changing the coefficient of a basic variable moves every reduced cost by the entry of
+        // its row and the basis holds for as long as none of them turns negative.
+        Some(row) => {
+            let tolerances = table.get_tolerances();
+            let mut lower = None;
+            let mut upper = None;
+            for &non_basic_column in non_basic_columns {
+                let entry = table_rows[row][non_basic_column].clone();
+                let reduced_cost = function_row[non_basic_column].clone();
+                if tolerances.is_positive(&entry) {
+                    lower = tighter(lower, -reduced_cost / entry, |new, old| new > old);
+                } else if tolerances.is_negative(&entry) {
+                    upper = tighter(upper, reduced_cost / -entry, |new, old| new < old);
+                }
+            }
+            (lower, upper)
+        }
+    }
+}
+
+pub fn get_rhs_delta_range<N: Num>(marker_column: usize,
+                                   sign: N,
+                                   table: &Table<N>)
+                                   -> (Option<N>, Option<N>) {
+    let table_rows = table.get_rows();
+    let rhs_column = table.get_column_names().len() - 1;
+    let num_constraint_rows = table_rows.len() - table.get_num_fun_rows();
+    // Each unit of RHS moves the basic variables by the marker column (up to its sign) and the
+    // basis holds for as long as none of them turns negative or passes its upper bound.
+    let tolerances = table.get_tolerances();
+    let mut lower = None;
+    let mut upper = None;
+    for (i, row) in table_rows.iter().enumerate().take(num_constraint_rows) {
+        let rate = sign.clone() * row[marker_column].clone();
+        let room = table.get_basic_var_of_row(i)
+            .and_then(|column| table.get_width(column))
+            .map(|width| width - row[rhs_column].clone());
+        if tolerances.is_positive(&rate) {
+            lower = tighter(lower, -row[rhs_column].clone() / rate.clone(), |new, old| new > old);
+            if let Some(room) = room {
+                upper = tighter(upper, room / rate, |new, old| new < old);
+            }
+        } else if tolerances.is_negative(&rate) {
+            upper = tighter(upper, row[rhs_column].clone() / -rate.clone(), |new, old| new < old);
+            if let Some(room) = room {
+                lower = tighter(lower, room / rate, |new, old| new > old);
+            }
+        }
+    }
+    (lower, upper)
+}
+
+fn tighter<N, F>(bound: Option<N>, candidate: N, is_tighter: F) -> Option<N>
+    where N: Num,
+          F: Fn(&N, &N) -> bool
+{
+    match bound {
+        Some(current) if !is_tighter(&candidate, &current) => Some(current),
+        _ => Some(candidate),
+    }
+}
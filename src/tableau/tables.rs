@@ -1,28 +1,78 @@
 use std::collections::HashMap;
 use std::result::Result;
-use math::variables::is_gen_arti_var;
+use math::variables::{AbstVar, Name, VarKey};
+use tableau::tolerances::Tolerances;
+use errors::SolveError;
 use Num;
 
-pub struct Table {
-    column_names: HashMap<String, usize>, // assume last column reserved
-    rows: Vec<Vec<Num>>,
+#[derive(Clone)]
+pub struct Table<N> {
+    column_names: HashMap<Name, usize>, // assume last column reserved
+    rows: Vec<Vec<N>>,
     num_fun_rows: usize,
+    tolerances: Tolerances<N>,
+    // The column of the basic variable of every row, function rows included.
+    basis: Vec<Option<usize>>,
+    // The bounds of every column but the RHS, for the variables that have any.
+    bounds: Vec<Option<Bound<N>>>,
+    // What kind of variable every column but the RHS holds.
+    kinds: Vec<ColumnKind>,
 }
 
-impl Table {
-    pub fn new(c_n: HashMap<String, usize>, r: Vec<Vec<Num>>) -> Table {
-        Table {
+/// Whether a column holds a variable of the model, one the solver added to turn its constraints
+/// into equations or an artificial one that only helps find a first feasible basis.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ColumnKind {
+    Decision,
+    Internal,
+    Artificial,
+}
+
+impl ColumnKind {
+    pub fn of<N>(var: &AbstVar<N>) -> ColumnKind {
+        match *var {
+            AbstVar::Variable { .. } |
+            AbstVar::Constant { .. } => ColumnKind::Decision,
+            AbstVar::SlackVar { .. } |
+            AbstVar::SurplusVar { .. } |
+            AbstVar::ErrorVar { .. } => ColumnKind::Internal,
+            AbstVar::ArtiVar { .. } => ColumnKind::Artificial,
+        }
+    }
+}
+
+// The rows count a bounded variable from its lower bound, so they hold the distance above it,
+// or the distance below its upper bound once the column has been complemented.
+#[derive(Clone)]
+struct Bound<N> {
+    lower: N,
+    upper: Option<N>,
+    complemented: bool,
+}
+
+impl<N: Num> Table<N> {
+    pub fn new<K: Into<Name>>(c_n: HashMap<K, usize>, r: Vec<Vec<N>>) -> Table<N> {
+        let c_n = c_n.into_iter()
+            .map(|(name, index)| (name.into(), index))
+            .collect::<HashMap<Name, usize>>();
+        let mut table = Table {
+            basis: vec![None; r.len()],
+            bounds: vec![None; c_n.len() - 1],
+            kinds: vec![ColumnKind::Decision; c_n.len() - 1],
             rows: r,
             column_names: c_n,
             num_fun_rows: 1,
-        }
+            tolerances: Tolerances::default(),
+        };
+        table.find_initial_basis();
+        table
     }
 
-    pub fn get_column_names(&self) -> &HashMap<String, usize> {
+    pub fn get_column_names(&self) -> &HashMap<Name, usize> {
         &self.column_names
     }
 
-    pub fn get_rows(&self) -> &Vec<Vec<Num>> {
+    pub fn get_rows(&self) -> &Vec<Vec<N>> {
         &self.rows
     }
 
@@ -30,132 +80,281 @@ impl Table {
         self.num_fun_rows
     }
 
-    pub fn get_basic_solution(&self) -> Result<Vec<(String, Num)>, (usize, usize)> {
-        let mut basic_solution = Vec::with_capacity(self.column_names.len());
-        // Note: ignore RHS column.
-        'columns: for i in 0..self.column_names.len() - 1 {
-            let mut one_entry_index = 0;
-            let mut matched_one = false;
-            // Find columns that have exactly one 1.0 and rest 0.0 values...
-            'column_entries: for j in 0..self.rows.len() {
-                match (matched_one, self.rows[j][i]) {
-                    (false, 1.0) | (false, -1.0) => {
-                        one_entry_index = j;
-                        matched_one = true;
-                    }
-                    (true, 0.0) | (false, 0.0) => continue 'column_entries,
-                    _ => continue 'columns,
-                }
+    pub fn get_tolerances(&self) -> &Tolerances<N> {
+        &self.tolerances
+    }
+
+    pub fn get_basis(&self) -> &Vec<Option<usize>> {
+        &self.basis
+    }
+
+    /// How far the variable of a column can move between its bounds, `None` without an upper
+    /// bound.
+    pub fn get_width(&self, column: usize) -> Option<N> {
+        match self.bounds[column] {
+            Some(Bound { ref lower, upper: Some(ref upper), .. }) => {
+                Some(upper.clone() - lower.clone())
             }
-            // ... and when we find a basic variable calculate its value but
-            // watch out as it might be all zeros so check if we matched a 1.0.
-            if matched_one {
-                let basic_variable_value = self.rows[one_entry_index][i] *
-                                           self.rows[one_entry_index][self.column_names.len() - 1];
-                // If the basic variable turns out negative that this solution
-                // is not feasable... (This applies to GEQ constraints not function rows.)
-                if basic_variable_value != 0.0 && basic_variable_value.is_sign_negative() &&
-                   one_entry_index < self.rows.len() - self.num_fun_rows {
-                    // ... report the row where it happened.
-                    return Err((one_entry_index, i));
-                } else {
-                    // ... if not continue generating the solution.
-                    basic_solution.push((get_name_of_index(&self.column_names, i)
-                                         .expect("get_basic_solution: Name not found for index \
-                                         given."),
-                                         basic_variable_value));
-                }
+            _ => None,
+        }
+    }
+
+    pub fn is_complemented(&self, column: usize) -> bool {
+        self.bounds[column].as_ref().is_some_and(|bound| bound.complemented)
+    }
+
+    /// The value of the variable of a column that sits at `value` in the table.
+    pub fn get_value_of(&self, column: usize, value: N) -> N {
+        match self.bounds[column] {
+            Some(ref bound) if bound.complemented => {
+                let width = self.get_width(column).unwrap_or_else(N::zero);
+                bound.lower.clone() + width - value
+            }
+            Some(ref bound) => bound.lower.clone() + value,
+            None => value,
+        }
+    }
+
+    pub fn get_basic_solution(&self) -> Result<Vec<(Name, N)>, (usize, usize)> {
+        let rhs_column = self.column_names.len() - 1;
+        let mut names = vec![None; rhs_column];
+        for (name, &index) in &self.column_names {
+            if index != rhs_column {
+                names[index] = Some(name);
+            }
+        }
+        let mut basic_vars = self.basis
+            .iter()
+            .enumerate()
+            .filter_map(|(row, column)| column.map(|column| (column, row)))
+            .collect::<Vec<(usize, usize)>>();
+        basic_vars.sort();
+        let mut basic_solution = Vec::with_capacity(basic_vars.len());
+        for (column, row) in basic_vars {
+            // A -1.0 entry stands in for the basic variable of a row that had no proper one.
+            let rhs = self.rows[row][rhs_column].clone();
+            let basic_variable_value = if self.tolerances.is_one(&self.rows[row][column]) {
+                rhs
+            } else {
+                -rhs
+            };
+            // If the basic variable turns out negative that this solution
+            // is not feasable... (This applies to GEQ constraints not function rows.)
+            if self.tolerances.is_negative(&basic_variable_value) &&
+               row < self.rows.len() - self.num_fun_rows {
+                // ... report the row where it happened.
+                return Err((row, column));
+            }
+            // ... if not continue generating the solution.
+            if let Some(name) = names[column] {
+                basic_solution.push((name.clone(), basic_variable_value));
             }
         }
         // If we got here then solution is feasable so return it.
         Ok(basic_solution)
     }
 
-    pub fn get_row_of_basic_var(&self, b_var_name: &String) -> usize {
-        let column = *self.column_names.get(b_var_name)
-            .expect("Basic variable name supplied does not exist.");
-        let mut basic_var_row = 0;
-        let mut matched_one = false;
-        for i in 0..self.rows.len() {
-            match (matched_one, self.rows[i][column]) {
-                (false, 0.0) | (true, 0.0) => continue,
-                (false, 1.0) => {
-                    matched_one = true;
-                    basic_var_row = i;
-                }
-                _ => panic!("Named variable is not basic."),
+    pub fn get_row_of_basic_var<K>(&self, b_var_name: &K) -> Result<usize, SolveError<N>>
+        where K: VarKey + ?Sized
+    {
+        let b_var_name = b_var_name.var_key();
+        let column = match self.column_names.get(&b_var_name) {
+            Some(index) => *index,
+            None => {
+                let reason = format!("\"{}\" is not a column of the table.", b_var_name);
+                return Err(SolveError::MalformedModel(reason));
+            }
+        };
+        match self.basis.iter().position(|basic_column| *basic_column == Some(column)) {
+            Some(row) => Ok(row),
+            None => {
+                let reason = format!("\"{}\" is not a basic variable.", b_var_name);
+                Err(SolveError::MalformedModel(reason))
             }
         }
-        basic_var_row
     }
 
-    pub fn is_solution_optimal(&self) -> bool {
-        // Make sure we do not consider pivoting in on artificial variables in Phase II.
+    pub fn is_solution_optimal(&self) -> Result<bool, SolveError<N>> {
+        // Make sure we do not consider pivoting in on artificial variables.
         let valid_cells = match self.num_fun_rows {
-            2 => (0..self.column_names.len() - 1).collect::<Vec<usize>>(),
-            1 => {
-                let arti_var_indexes = self.column_names
-                    .iter()
-                    .filter(|&(key, _)| is_gen_arti_var(key))
-                    .map(|(_, index)| index.clone())
-                    .collect::<Vec<usize>>();
-                if arti_var_indexes.is_empty() {
-                    (0..self.column_names.len() - 1).collect::<Vec<usize>>()
-                } else {
-                    (0..self.column_names.len() - 1)
-                        .filter(|index| !arti_var_indexes.contains(index))
-                        .collect::<Vec<usize>>()
-                }
+            1 | 2 => {
+                let arti_var_indexes = self.get_excluded_columns();
+                (0..self.column_names.len() - 1)
+                    .filter(|index| !arti_var_indexes.contains(index))
+                    .collect::<Vec<usize>>()
+            }
+            n => {
+                let reason = format!("expected 1 or 2 functions in table, found {}.", n);
+                return Err(SolveError::MalformedModel(reason));
             }
-            _ => panic!("is_solution_optimal: expected 1 or 2 functions in table."),
         };
 
         for i in valid_cells {
-            if self.rows[self.rows.len() - 1][i].is_sign_negative() {
-                return false;
+            if self.is_basic(i) {
+                continue;
+            }
+            if self.tolerances.is_improving_cost(&self.rows[self.rows.len() - 1][i]) {
+                return Ok(false);
             }
         }
-        true
+        Ok(true)
+    }
+
+    pub fn get_basic_var_of_row(&self, row_index: usize) -> Option<usize> {
+        self.basis[row_index]
+    }
+
+    pub fn is_basic(&self, column: usize) -> bool {
+        self.basis.contains(&Some(column))
+    }
+
+    pub fn get_excluded_columns(&self) -> Vec<usize> {
+        // Artificial variables are never pivoted back in once they have left the basis.
+        (0..self.kinds.len())
+            .filter(|&column| self.kinds[column] == ColumnKind::Artificial)
+            .collect::<Vec<usize>>()
+    }
+
+    pub fn get_column_kind(&self, column: usize) -> ColumnKind {
+        self.kinds[column]
     }
 
-    pub fn append_empty_column(&mut self, c_name: String) {
+    pub fn set_column_kind(&mut self, column: usize, kind: ColumnKind) {
+        self.kinds[column] = kind;
+    }
+
+    pub fn append_empty_column(&mut self, c_name: Name) {
         // Take away 1 because the RHS is at the end.
         let map_len = self.column_names.len();
         self.column_names.insert(c_name, map_len - 1);
         // Make the RHS point to last cell again.
-        self.column_names.insert("RHS".to_string(), map_len);
+        self.column_names.insert(Name::from("RHS"), map_len);
         let rhs_column_index = self.rows[0].len() - 1;
         for row in 0..self.rows.len() {
-            self.rows[row].insert(rhs_column_index, 0.0);
+            self.rows[row].insert(rhs_column_index, N::zero());
         }
+        self.bounds.insert(rhs_column_index, None);
+        self.kinds.insert(rhs_column_index, ColumnKind::Decision);
     }
 
-    pub fn append_row(&mut self, row: Vec<Num>) {
+    // New rows have no basic variable until one is set for them.
+    pub fn append_row(&mut self, row: Vec<N>) {
         self.rows.push(row);
+        self.basis.push(None);
+    }
+
+    pub fn insert_row(&mut self, row_index: usize, row: Vec<N>) {
+        self.rows.insert(row_index, row);
+        self.basis.insert(row_index, None);
     }
 
-    pub fn remove_last_row(&mut self) {
-        self.rows.pop().expect("Failed to remove last row from table.");
+    pub fn remove_row(&mut self, row_index: usize) -> Vec<N> {
+        self.basis.remove(row_index);
+        self.rows.remove(row_index)
+    }
+
+    pub fn remove_last_row(&mut self) -> Option<Vec<N>> {
+        self.basis.pop();
+        self.rows.pop()
+    }
+
+    pub fn remove_column<K: VarKey + ?Sized>(&mut self, c_name: &K) -> Option<usize> {
+        let removed_index = self.column_names.remove(&c_name.var_key())?;
+        for index in self.column_names.values_mut() {
+            if *index > removed_index {
+                *index -= 1;
+            }
+        }
+        for row in &mut self.rows {
+            row.remove(removed_index);
+        }
+        self.bounds.remove(removed_index);
+        self.kinds.remove(removed_index);
+        for basic_column in &mut self.basis {
+            *basic_column = match *basic_column {
+                Some(column) if column == removed_index => None,
+                Some(column) if column > removed_index => Some(column - 1),
+                other => other,
+            };
+        }
+        Some(removed_index)
     }
 
     pub fn set_num_fun_rows(&mut self, num_rows: usize) {
         self.num_fun_rows = num_rows;
     }
 
-    pub fn sub_cell(&mut self, row_index: usize, colunm_index: usize, by: Num) {
-        self.rows[row_index][colunm_index] = self.rows[row_index][colunm_index] - by;
+    pub fn set_tolerances(&mut self, tolerances: Tolerances<N>) {
+        self.tolerances = tolerances;
     }
 
-    pub fn div_cell(&mut self, row_index: usize, colunm_index: usize, by: Num) {
-        self.rows[row_index][colunm_index] = self.rows[row_index][colunm_index] / by;
+    pub fn set_basic_var_of_row(&mut self, row_index: usize, column: usize) {
+        self.basis[row_index] = Some(column);
+    }
+
+    /// Bounds the variable of a column whose entries are already counted from `lower`.
+    pub fn set_bound(&mut self, column: usize, lower: N, upper: Option<N>) {
+        self.bounds[column] = Some(Bound {
+            lower,
+            upper,
+            complemented: false,
+        });
+    }
+
+    /// Counts the variable of a column down from its upper bound rather than up from its lower
+    /// one, or back again. A basic variable keeps a unit entry in its row.
+    pub fn complement_column(&mut self, column: usize) {
+        let width = match self.get_width(column) {
+            Some(width) => width,
+            None => return,
+        };
+        let rhs_column = self.column_names.len() - 1;
+        for row in &mut self.rows {
+            let entry = row[column].clone();
+            row[rhs_column] -= entry.clone() * width.clone();
+            row[column] = -entry;
+        }
+        if let Some(row_index) = self.basis.iter().position(|basic| *basic == Some(column)) {
+            for cell in &mut self.rows[row_index] {
+                *cell = -cell.clone();
+            }
+        }
+        if let Some(ref mut bound) = self.bounds[column] {
+            bound.complemented = !bound.complemented;
+        }
+    }
+
+    pub fn set_cell(&mut self, row_index: usize, colunm_index: usize, to: N) {
+        self.rows[row_index][colunm_index] = to;
+    }
+
+    pub fn sub_cell(&mut self, row_index: usize, colunm_index: usize, by: N) {
+        self.rows[row_index][colunm_index] -= by;
+    }
+
+    pub fn div_cell(&mut self, row_index: usize, colunm_index: usize, by: N) {
+        self.rows[row_index][colunm_index] = self.rows[row_index][colunm_index].clone() / by;
     }
-}
 
-fn get_name_of_index(c_n: &HashMap<String, usize>, index: usize) -> Option<String> {
-    for (key, val) in c_n.iter() {
-        if *val == index {
-            return Some(key.clone());
+    fn find_initial_basis(&mut self) {
+        // Only done once: from here on pivots keep the basis up to date. Tables built from a
+        // model have their basis set from the variables each row was built with; for any other
+        // prefer a column with a 1.0 in the row and 0.0 everywhere else and fall back on a -1.0
+        // one, as a surplus variable has before the row is made feasible.
+        let num_columns = self.column_names.len() - 1;
+        for row_index in 0..self.rows.len() {
+            let is_unit_column = |column: usize, sign: N| {
+                self.tolerances.is_one(&(sign * self.rows[row_index][column].clone())) &&
+                (0..self.rows.len())
+                    .all(|i| i == row_index || self.tolerances.is_zero(&self.rows[i][column]))
+            };
+            let basic_column = (0..num_columns)
+                .find(|&column| !self.is_basic(column) && is_unit_column(column, N::one()))
+                .or_else(|| {
+                    (0..num_columns)
+                        .find(|&column| !self.is_basic(column) && is_unit_column(column, -N::one()))
+                });
+            self.basis[row_index] = basic_column;
         }
     }
-    None
 }
@@ -0,0 +1,62 @@
+use Num;
+
+/// How close to zero or one a cell has to be to count as it. Values from a long run of pivots
+/// pick up rounding error, so exact comparisons would miss basic variables and optima.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Tolerances<N> {
+    // Applies to the constraint rows: entries, RHS values and pivot candidates.
+    feasibility: N,
+    // Applies to the reduced costs in the function row.
+    optimality: N,
+}
+
+impl<N: Num> Tolerances<N> {
+    pub fn new(feasibility: N, optimality: N) -> Tolerances<N> {
+        Tolerances {
+            feasibility,
+            optimality,
+        }
+    }
+
+    pub fn feasibility(&self) -> &N {
+        &self.feasibility
+    }
+
+    pub fn optimality(&self) -> &N {
+        &self.optimality
+    }
+
+    pub fn is_zero(&self, value: &N) -> bool {
+        within(value.clone(), &self.feasibility)
+    }
+
+    pub fn is_one(&self, value: &N) -> bool {
+        within(value.clone() - N::one(), &self.feasibility)
+    }
+
+    pub fn is_positive(&self, value: &N) -> bool {
+        *value > self.feasibility
+    }
+
+    pub fn is_negative(&self, value: &N) -> bool {
+        *value < -self.feasibility.clone()
+    }
+
+    pub fn is_zero_cost(&self, reduced_cost: &N) -> bool {
+        within(reduced_cost.clone(), &self.optimality)
+    }
+
+    pub fn is_improving_cost(&self, reduced_cost: &N) -> bool {
+        *reduced_cost < -self.optimality.clone()
+    }
+}
+
+impl<N: Num> Default for Tolerances<N> {
+    fn default() -> Tolerances<N> {
+        Tolerances::new(N::tolerance(), N::tolerance())
+    }
+}
+
+fn within<N: Num>(value: N, tolerance: &N) -> bool {
+    -tolerance.clone() <= value && value <= *tolerance
+}
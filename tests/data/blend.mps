@@ -0,0 +1,23 @@
+* A small blending model: the cheapest mix meeting every limit.
+NAME          BLEND
+ROWS
+ N  COST
+ G  LIM1
+ L  LIM2
+ E  MYEQN
+COLUMNS
+    X         COST      2              LIM1      1
+    X         LIM2      1
+    Y         COST      3              LIM1      1
+    Y         MYEQN     1
+    Z         COST      1              LIM2      1
+    Z         MYEQN     -1
+RHS
+    RHS       LIM1      4              LIM2      6
+    RHS       MYEQN     1
+RANGES
+    RNG       LIM2      4
+BOUNDS
+ UP BND       X         3
+ LO BND       Z         1
+ENDATA
@@ -0,0 +1,81 @@
+extern crate cassowary;
+
+use cassowary::math::numbers::Rational;
+use cassowary::format::lp::{parse_lp, write_lp};
+
+const MODEL: &str = "\\ Three products sharing machine time
+Maximize
+ P: 2.5 x + 3 y
+Subject To
+ cutting: 3 x + 6 y <= 90
+ sewing: 2 x + y <= 35
+ x + y <= 20
+Bounds
+ x >= 0
+ y >= 0
+End
+";
+
+#[test]
+fn format_test_lp_solve() {
+    let (mut objective_func, mut subject_to) = parse_lp::<f64>(MODEL).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.value("y"));
+}
+
+#[test]
+fn format_test_lp_round_trip() {
+    let (objective_func, subject_to) = parse_lp::<Rational>(MODEL).unwrap();
+    let text = write_lp(&objective_func, &subject_to).unwrap();
+    assert_eq!("Maximize\n P: 2.5 x + 3 y\n\
+                Subject To\n cutting: 3 x + 6 y <= 90\n sewing: 2 x + y <= 35\n c3: x + y <= 20\n\
+                Bounds\n x >= 0\n y >= 0\n\
+                End\n",
+               text);
+    let (mut objective_func, mut subject_to) = parse_lp::<Rational>(&text).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Rational::new(55, 1), solution.objective());
+}
+
+#[test]
+fn format_test_lp_write_after_solving() {
+    let (mut objective_func, mut subject_to) = parse_lp::<f64>(MODEL).unwrap();
+    cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    let text = write_lp(&objective_func, &subject_to).unwrap();
+    assert_eq!(write_lp(&parse_lp::<f64>(MODEL).unwrap().0, &parse_lp(MODEL).unwrap().1).unwrap(),
+               text);
+}
+
+#[test]
+fn format_test_lp_errors() {
+    let error = parse_lp::<f64>("Minimize\n cost: 2 x + 3 y\nSubject To\n x + y >= ten\n")
+        .err()
+        .unwrap();
+    assert_eq!(4, error.line());
+    assert_eq!(11, error.column());
+    assert_eq!("line 4, column 11: expected a number.", error.to_string());
+
+    let error = parse_lp::<f64>("Minimize\n cost: 2 x + 3 y\nSubject To\n x + y >= 1\n\
+                                 Bounds\n -inf <= x <= 4\n")
+        .err()
+        .unwrap();
+    assert_eq!((6, 3), (error.line(), error.column()));
+
+    let error = parse_lp::<f64>("Maximize\n P: x + v#3\nSubject To\n x <= 1\n").err().unwrap();
+    assert_eq!("line 2, column 9: \"v#3\" reads like a variable handle.",
+               error.to_string());
+
+    // Numbers out of range are turned down rather than read as infinity or built for ages.
+    let error = parse_lp::<f64>("Maximize\n P: x\nSubject To\n x <= 1e999\n").err().unwrap();
+    assert_eq!((4, 7), (error.line(), error.column()));
+    let error = parse_lp::<Rational>("Maximize\n P: x\nSubject To\n x <= 1e999999999\n")
+        .err()
+        .unwrap();
+    assert_eq!((4, 7), (error.line(), error.column()));
+    let error = parse_lp::<Rational>("Maximize\n P: x\nSubject To\n x <= 1.5e-2147483648\n")
+        .err()
+        .unwrap();
+    assert_eq!((4, 7), (error.line(), error.column()));
+}
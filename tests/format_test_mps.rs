@@ -0,0 +1,132 @@
+extern crate cassowary;
+
+use cassowary::math::numbers::Rational;
+use cassowary::format::mps::{MpsFormat, parse_mps, write_mps};
+
+const BLEND: &str = include_str!("data/blend.mps");
+
+const PRODUCTION: &str = "NAME production
+OBJSENSE MAX
+ROWS
+ N profit
+ L cutting_hours
+ L sewing_hours
+ L total_units
+COLUMNS
+ shirts profit 2.5 cutting_hours 3
+ shirts sewing_hours 2 total_units 1
+ trousers profit 3 cutting_hours 6
+ trousers sewing_hours 1 total_units 1
+RHS
+ cutting_hours 90 sewing_hours 35
+ total_units 20
+ENDATA
+";
+
+#[test]
+fn format_test_mps_fixed() {
+    let (mut objective_func, mut subject_to) = parse_mps::<f64>(BLEND, MpsFormat::Fixed).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(11.0, solution.objective());
+    assert_eq!(Some(2.0), solution.value("X"));
+    assert_eq!(Some(2.0), solution.value("Y"));
+    assert_eq!(Some(1.0), solution.value("Z"));
+}
+
+#[test]
+fn format_test_mps_free() {
+    let (mut objective_func, mut subject_to) = parse_mps::<f64>(PRODUCTION, MpsFormat::Free)
+        .unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("shirts"));
+    assert_eq!(Some(10.0), solution.value("trousers"));
+
+    // Without names longer than eight characters, fixed format files read as free ones.
+    let (mut objective_func, mut subject_to) = parse_mps::<f64>(BLEND, MpsFormat::Free).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(11.0, solution.objective());
+}
+
+#[test]
+fn format_test_mps_round_trip() {
+    let (objective_func, subject_to) = parse_mps::<Rational>(BLEND, MpsFormat::Fixed).unwrap();
+    let text = write_mps(&objective_func, &subject_to, MpsFormat::Fixed).unwrap();
+    assert_eq!("NAME
+ROWS
+ N  COST
+ G  LIM1
+ L  LIM2
+ G  c3
+ E  MYEQN
+COLUMNS
+    X         COST      2
+    X         LIM1      1
+    X         LIM2      1
+    X         c3        1
+    Y         COST      3
+    Y         LIM1      1
+    Y         MYEQN     1
+    Z         COST      1
+    Z         LIM2      1
+    Z         c3        1
+    Z         MYEQN     -1
+RHS
+    RHS       LIM1      4
+    RHS       LIM2      6
+    RHS       c3        2
+    RHS       MYEQN     1
+BOUNDS
+ UP BND       X         3
+ LO BND       Z         1
+ENDATA
+",
+               text);
+    let (mut objective_func, mut subject_to) = parse_mps::<Rational>(&text, MpsFormat::Fixed)
+        .unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Rational::new(11, 1), solution.objective());
+
+    let (mut objective_func, mut subject_to) = parse_mps::<f64>(PRODUCTION, MpsFormat::Free)
+        .unwrap();
+    cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    let text = write_mps(&objective_func, &subject_to, MpsFormat::Free).unwrap();
+    assert!(text.starts_with("NAME\nOBJSENSE\n    MAX\nROWS\n N profit\n L cutting_hours\n"));
+    let (mut objective_func, mut subject_to) = parse_mps::<f64>(&text, MpsFormat::Free).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(55.0, solution.objective());
+
+    // Names too long for fixed fields give way to numbered ones where they can.
+    let text = write_mps(&objective_func, &subject_to, MpsFormat::Fixed).unwrap();
+    assert!(text.contains("\n L  c1\n L  c2\n L  c3\n"));
+}
+
+#[test]
+fn format_test_mps_errors() {
+    let text = "ROWS\n N  COST\n L  LIM1\nCOLUMNS\n    X  COST  1  LIM2  1\n";
+    let error = parse_mps::<f64>(text, MpsFormat::Free).err().unwrap();
+    assert_eq!("line 5, column 17: unknown row \"LIM2\".", error.to_string());
+
+    let text = "ROWS\n N  COST\nCOLUMNS\n    MARKER  'MARKER'  'INTORG'\n";
+    let error = parse_mps::<f64>(text, MpsFormat::Free).err().unwrap();
+    assert_eq!((4, 5), (error.line(), error.column()));
+
+    let text = "ROWS\n N  COST\nCOLUMNS\n    X  COST  1\nBOUNDS\n MI BND  X\n";
+    let error = parse_mps::<f64>(text, MpsFormat::Free).err().unwrap();
+    assert_eq!((6, 2), (error.line(), error.column()));
+
+    let text = "ROWS\n N  COST\nCOLUMNS\n    v#3  COST  1\n";
+    let error = parse_mps::<f64>(text, MpsFormat::Free).err().unwrap();
+    assert_eq!((4, 5), (error.line(), error.column()));
+
+    // A negative upper bound without a lower one leaves the column free below.
+    let text = "ROWS\n N  COST\nCOLUMNS\n    X  COST  1\nBOUNDS\n UP BND  X  -1\n";
+    let error = parse_mps::<f64>(text, MpsFormat::Free).err().unwrap();
+    assert_eq!("line 6, column 2: variables need a finite lower bound, so free ones are not \
+                supported.",
+               error.to_string());
+    let text = "ROWS\n N  COST\nCOLUMNS\n    X  COST  1\nBOUNDS\n LO BND  X  -5\n UP BND  X  -1\n";
+    let (mut function, mut constraints) = parse_mps::<f64>(text, MpsFormat::Free).unwrap();
+    let solution = cassowary::optimise(&mut function, &mut constraints).unwrap();
+    assert_eq!(Some(-5.0), solution.value("X"));
+}
@@ -0,0 +1,295 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_weighted_con, new_stay_con};
+use cassowary::objective::strengths::Strength;
+use cassowary::incremental::solvers::Solver;
+use cassowary::errors::SolveError;
+use cassowary::options::{Options, CancellationToken};
+
+#[test]
+fn incremental_test_add_remove_1() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 4.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    let c1 = solver.add_constraint(new_reg_con(exp2)).unwrap();
+    let c2 = solver.add_constraint(new_reg_con(exp3)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(4.0), solution.value("x"));
+    assert_eq!(Some(6.0), solution.value("y"));
+    assert_eq!(26.0, solution.objective());
+
+    solver.remove_constraint(&c2).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(20.0, solution.objective());
+
+    solver.remove_constraint(&c1).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(0.0, solution.objective());
+}
+
+#[test]
+fn incremental_test_add_remove_2() {
+    // Removing a constraint whose marker is not basic still gives back the old optimum.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 2.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con2", 3.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    let c1 = solver.add_constraint(new_reg_con(exp2)).unwrap();
+    let c2 = solver.add_constraint(new_reg_con(exp3)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(7.0), solution.value("x"));
+    assert_eq!(Some(3.0), solution.value("y"));
+    assert_eq!(13.0, solution.objective());
+
+    solver.remove_constraint(&c1).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(3.0), solution.value("y"));
+    assert_eq!(6.0, solution.objective());
+
+    solver.remove_constraint(&c2).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(0.0, solution.objective());
+}
+
+#[test]
+fn incremental_test_unbounded() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let exp3 = Expression::new(vec![new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 3.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MAX)).unwrap();
+    match solver.solution() {
+        Err(SolveError::Unbounded(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    solver.add_constraint(new_reg_con(exp3)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(4.0), solution.value("x"));
+    assert_eq!(Some(3.0), solution.value("y"));
+    assert_eq!(7.0, solution.objective());
+}
+
+#[test]
+fn incremental_test_infeasible() {
+    // A required constraint that can not hold is refused and the solver carries on as before.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 5.0)]);
+    let exp4 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    assert_eq!(Err(SolveError::Infeasible),
+               solver.add_constraint(new_reg_con(exp3)));
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(10.0), solution.value("x"));
+    solver.add_constraint(new_reg_con(exp4)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.internal_value("sl2"));
+}
+
+#[test]
+fn incremental_test_strengths() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("left", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("left", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con2", 20.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    solver.add_constraint(new_weighted_con(exp2, Strength::Medium)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(10.0), solution.value("left"));
+
+    let c2 = solver.add_constraint(new_weighted_con(exp3, Strength::Strong)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(20.0), solution.value("left"));
+    assert_eq!(10_000.0, solution.objective());
+
+    solver.remove_constraint(&c2).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(10.0), solution.value("left"));
+    assert_eq!(0.0, solution.objective());
+}
+
+#[test]
+fn incremental_test_edit_vars() {
+    // Drag the left edge of a box whose right edge is pinned, keeping a minimum width.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0), new_var("width", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("left", 1.0), new_var("width", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 100.0)]);
+    let exp3 = Expression::new(vec![new_var("width", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con2", 10.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    solver.add_constraint(new_reg_con(exp3)).unwrap();
+    solver.add_edit_var("left", Strength::Strong).unwrap();
+    assert!(solver.has_edit_var("left"));
+
+    solver.suggest_value("left", 30.0).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(30.0), solution.value("left"));
+    assert_eq!(Some(70.0), solution.value("width"));
+
+    solver.suggest_value("left", 95.0).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(90.0), solution.value("left"));
+    assert_eq!(Some(10.0), solution.value("width"));
+
+    solver.suggest_value("left", 50.0).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(50.0), solution.value("left"));
+    assert_eq!(Some(50.0), solution.value("width"));
+    assert_eq!(0.0, solution.objective());
+
+    solver.remove_edit_var("left").unwrap();
+    assert!(!solver.has_edit_var("left"));
+    match solver.suggest_value("left", 20.0) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn incremental_test_edit_vars_required() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 0.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    match solver.add_edit_var("x", Strength::Required) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    solver.add_edit_var("x", Strength::Weak).unwrap();
+    solver.suggest_value("x", 7.0).unwrap();
+    assert_eq!(Some(7.0), solver.solution().unwrap().value("x"));
+}
+
+#[test]
+fn incremental_test_edit_vars_cancelled() {
+    // Once the token is cancelled a suggestion that needs the dual simplex is turned down and
+    // the solver stays where it was.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0), new_var("width", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("left", 1.0), new_var("width", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 100.0)]);
+    let exp3 = Expression::new(vec![new_var("width", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con2", 10.0)]);
+    let token = CancellationToken::new();
+    let mut options = Options::default();
+    options.set_cancellation_token(Some(token.clone()));
+    let mut solver = Solver::with_options(Function::new(exp1, ProblemType::MIN), &options)
+        .unwrap();
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    solver.add_constraint(new_reg_con(exp3)).unwrap();
+    solver.add_edit_var("left", Strength::Strong).unwrap();
+    solver.suggest_value("left", 30.0).unwrap();
+
+    token.cancel();
+    assert_eq!(Err(SolveError::Cancelled), solver.suggest_value("left", 95.0));
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(30.0), solution.value("left"));
+    assert_eq!(Some(70.0), solution.value("width"));
+}
+
+#[test]
+fn incremental_test_stays() {
+    // Once its width is no longer asked for the box keeps it while its left edge is dragged.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0), new_var("width", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("left", 1.0), new_var("width", 1.0),
+                                    new_var("right", -1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 0.0)]);
+    let exp3 = Expression::new(vec![new_var("width", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con2", 50.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    let c2 = solver.add_constraint(new_weighted_con(exp3, Strength::Medium)).unwrap();
+    solver.add_constraint(new_stay_con(new_var("left", 1.0))).unwrap();
+    solver.add_constraint(new_stay_con(new_var("width", 1.0))).unwrap();
+    solver.remove_constraint(&c2).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(50.0), solution.value("width"));
+    assert_eq!(Some(50.0), solution.value("right"));
+
+    solver.add_edit_var("left", Strength::Strong).unwrap();
+    solver.suggest_value("left", 30.0).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(30.0), solution.value("left"));
+    assert_eq!(Some(50.0), solution.value("width"));
+    assert_eq!(Some(80.0), solution.value("right"));
+    // The stay on "left" has moved along with it so it costs nothing.
+    assert_eq!(0.0, solution.objective());
+
+    solver.remove_edit_var("left").unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(30.0), solution.value("left"));
+    assert_eq!(Some(80.0), solution.value("right"));
+}
+
+#[test]
+fn incremental_test_duals() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 4.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    let c1 = solver.add_constraint(new_reg_con(exp2)).unwrap();
+    assert_eq!(Some(2.0), solver.dual(&c1));
+    let c2 = solver.add_constraint(new_reg_con(exp3)).unwrap();
+    assert_eq!(Some(3.0), solver.dual(&c1));
+    assert_eq!(Some(-1.0), solver.dual(&c2));
+    solver.remove_constraint(&c2).unwrap();
+    assert_eq!(None, solver.dual(&c2));
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+extern crate cassowary;
+extern crate serde_json;
+
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{Name, Variables, new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::functions::Function;
+use cassowary::objective::constraints::{SystemOfConstraints, new_reg_con, new_weighted_con};
+use cassowary::objective::strengths::Strength;
+use cassowary::solutions::Solution;
+use cassowary::errors::SolveError;
+
+#[test]
+fn serde_test_model_round_trip() {
+    let (objective_func, subject_to) = lp!(
+        maximize P = 2.5 x + 3 y;
+        3 x + 6 y <= 90;
+        2 x + y <= 35;
+        x + y <= 20;
+        x, y >= 0
+    );
+    let function_json = serde_json::to_string(&objective_func).unwrap();
+    let constraints_json = serde_json::to_string(&subject_to).unwrap();
+    let mut function: Function<f64> = serde_json::from_str(&function_json).unwrap();
+    let mut constraints: SystemOfConstraints<f64> = serde_json::from_str(&constraints_json)
+        .unwrap();
+    assert_eq!(function_json, serde_json::to_string(&function).unwrap());
+    assert_eq!(constraints_json, serde_json::to_string(&constraints).unwrap());
+
+    let solution = cassowary::optimise(&mut function, &mut constraints).unwrap();
+    assert_eq!(55.0, solution.objective());
+    let solution_json = serde_json::to_string(&solution).unwrap();
+    assert_eq!(solution, serde_json::from_str::<Solution<f64>>(&solution_json).unwrap());
+}
+
+#[test]
+fn serde_test_rational_round_trip() {
+    let exp = Expression::new(vec![new_var("Z", Rational::new(1, 1))],
+                              Relationship::EQ,
+                              vec![new_var("x", Rational::new(1, 3)),
+                                   new_var("y", Rational::new(-2, 1))]);
+    let mut function = Function::new(exp, ProblemType::MIN);
+    let mut constraints = SystemOfConstraints::new(vec![
+        new_reg_con(Expression::new(vec![new_var("x", Rational::new(1, 1)),
+                                         new_var("y", Rational::new(1, 1))],
+                                    Relationship::LEQ,
+                                    vec![new_const("RHS", Rational::new(7, 2))])),
+        new_weighted_con(Expression::new(vec![new_var("x", Rational::new(1, 1))],
+                                         Relationship::GEQ,
+                                         vec![new_const("RHS", Rational::new(1, 1))]),
+                         Strength::Custom(Rational::new(1, 10))),
+    ]);
+    let json = serde_json::to_string(&constraints).unwrap();
+    assert!(json.contains("\"7/2\""));
+    assert!(json.contains("\"1/10\""));
+    let mut read: SystemOfConstraints<Rational> = serde_json::from_str(&json).unwrap();
+    assert_eq!(json, serde_json::to_string(&read).unwrap());
+    let mut function_read: Function<Rational> =
+        serde_json::from_str(&serde_json::to_string(&function).unwrap()).unwrap();
+
+    let solution = cassowary::optimise(&mut function, &mut constraints).unwrap();
+    let solution_read = cassowary::optimise(&mut function_read, &mut read).unwrap();
+    assert_eq!(solution, solution_read);
+    let json = serde_json::to_string(&solution).unwrap();
+    assert_eq!(solution, serde_json::from_str::<Solution<Rational>>(&json).unwrap());
+
+    assert!(serde_json::from_str::<Rational>("\"two\"").is_err());
+}
+
+#[test]
+fn serde_test_error_round_trip() {
+    let y = Variables::new().add("y");
+    let error: SolveError<f64> = SolveError::Unbounded(vec![(Name::from("x"), 1.0),
+                                                            (Name::from(y), 2.0)]);
+    let json = serde_json::to_string(&error).unwrap();
+    assert!(json.contains("[\"x\",1.0]"));
+    assert_eq!(error, serde_json::from_str(&json).unwrap());
+}
@@ -0,0 +1,171 @@
+extern crate cassowary;
+
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_bounded_con, SystemOfConstraints};
+use cassowary::errors::SolveError;
+use cassowary::format::lp::{parse_lp, write_lp};
+
+#[test]
+fn simplex_test_bounds_upper() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 3.0), new_var("y", 2.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x", 1.0), 0.0, Some(1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(9.0, solution.objective());
+    assert_eq!(Some(1.0), solution.value("x"));
+    assert_eq!(Some(3.0), solution.value("y"));
+    // x sits at its upper bound and would add one more for every unit it could go further.
+    assert_eq!(Some(1.0), solution.reduced_cost("x"));
+    assert_eq!(Some(2.0), solution.dual(1));
+    // The bound is not a row, so the constraint is the only one with a dual.
+    assert_eq!(1, solution.duals().len());
+}
+
+#[test]
+fn simplex_test_bounds_basic_at_upper() {
+    // Raising y pushes x up to its upper bound before y reaches its own.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", -1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x", 1.0), 0.0, Some(3.0));
+    let c3 = new_bounded_con(new_var("y", 1.0), 0.0, Some(5.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(11.0, solution.objective());
+    assert_eq!(Some(3.0), solution.value("x"));
+    assert_eq!(Some(5.0), solution.value("y"));
+}
+
+#[test]
+fn simplex_test_bounds_negative_lower() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 2.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", -2.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x", 1.0), -5.0, Some(4.0));
+    let c3 = new_bounded_con(new_var("y", 1.0), -1.0, Some(2.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(-3.0, solution.objective());
+    assert_eq!(Some(-1.0), solution.value("x"));
+    assert_eq!(Some(-1.0), solution.value("y"));
+    assert_eq!(Some(1.0), solution.reduced_cost("y"));
+    assert_eq!(Some(1.0), solution.dual(1));
+}
+
+#[test]
+fn simplex_test_bounds_rational() {
+    let n = |value| Rational::new(value, 1);
+    let exp1 = Expression::new(vec![new_var("P", n(1))],
+                               Relationship::EQ,
+                               vec![new_var("x", n(1)), new_var("y", n(2))]);
+    let exp2 = Expression::new(vec![new_var("x", n(2)), new_var("y", n(2))],
+                               Relationship::LEQ,
+                               vec![new_const("con1", n(9))]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x", n(1)), n(0), Some(n(4)));
+    let c3 = new_bounded_con(new_var("y", n(1)), n(1), Some(n(3)));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Rational::new(15, 2), solution.objective());
+    assert_eq!(Some(Rational::new(3, 2)), solution.value("x"));
+    assert_eq!(Some(n(3)), solution.value("y"));
+}
+
+#[test]
+fn simplex_test_bounds_infeasible() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_bounded_con(new_var("x", 1.0), 0.0, Some(3.0));
+    let c2 = new_bounded_con(new_var("x", 1.0), 4.0, None);
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    assert_eq!(Err(SolveError::Infeasible),
+               cassowary::optimise(&mut objective_func, &mut subject_to).map(|_| ()));
+
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x", 1.0), 2.0, None);
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    assert_eq!(Err(SolveError::Infeasible),
+               cassowary::optimise(&mut objective_func, &mut subject_to).map(|_| ()));
+}
+
+#[test]
+fn simplex_test_bounds_leave_model_as_given() {
+    let text = "Maximize\n P: x + y\nSubject To\n c1: x + 2 y <= 6\n\
+                Bounds\n 1 <= x <= 2\n y >= -1\nEnd\n";
+    let (mut objective_func, mut subject_to) = parse_lp::<f64>(text).unwrap();
+    let before = write_lp(&objective_func, &subject_to).unwrap();
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(4.0, solution.objective());
+    // y only takes up the slack down to its own lower bound of -1.
+    let rhs_range = solution.rhs_range(1).unwrap();
+    assert_eq!((Some(0.0), None), (rhs_range.lower(), rhs_range.upper()));
+    assert_eq!(before, write_lp(&objective_func, &subject_to).unwrap());
+}
+
+#[test]
+fn simplex_test_bounds_equality() {
+    // x0 is the only unit column of its row but starts out at its lower bound, not at 7.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("RHS", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("x0", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 7.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x0", 1.0), 1.0, None);
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(0.0, solution.objective());
+    assert_eq!(Some(7.0), solution.value("x0"));
+}
+
+#[test]
+fn simplex_test_bounds_negative_rhs() {
+    // Once the row is negated x0 has to climb all the way from its lower bound to its upper one.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("RHS", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("x0", -1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", -6.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_bounded_con(new_var("x0", 1.0), 1.0, Some(6.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(0.0, solution.objective());
+    assert_eq!(Some(6.0), solution.value("x0"));
+}
@@ -0,0 +1,99 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+
+fn assert_dual(expected: f64, actual: Option<f64>) {
+    match actual {
+        Some(value) => assert!((expected - value).abs() < 1e-5, "{} != {}", expected, value),
+        None => panic!("Missing dual value, expected {}.", expected),
+    }
+}
+
+#[test]
+fn simplex_test_duals_max() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.5), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 3.0), new_var("y", 6.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 90.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 2.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 35.0)]);
+    let exp4 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(new_var("x", 1.0));
+    let c5 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_dual(1.0 / 6.0, solution.dual(1));
+    assert_dual(0.0, solution.dual(2));
+    assert_dual(2.0, solution.dual(3));
+    assert_eq!(None, solution.dual(4));
+    assert_eq!(3, solution.duals().len());
+}
+
+#[test]
+fn simplex_test_duals_min() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(26.0, solution.objective());
+    // Every unit more to cover costs a unit of y while every unit more of x saves one.
+    assert_dual(3.0, solution.dual(1));
+    assert_dual(-1.0, solution.dual(2));
+}
+
+#[test]
+fn simplex_test_duals_eq_and_negated() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 2.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 10.0)]);
+    let mut objective_func = Function::new(exp1.clone(), ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(10.0, solution.objective());
+    assert_dual(1.0, solution.dual(1));
+
+    // Same as x + y >= 10 but given with a negative RHS.
+    let exp3 = Expression::new(vec![new_var("x", -1.0), new_var("y", -1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", -10.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp3);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(10.0, solution.objective());
+    assert_dual(-1.0, solution.dual(1));
+}
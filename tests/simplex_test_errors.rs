@@ -0,0 +1,179 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{Name, new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::errors::SolveError;
+
+#[test]
+fn simplex_test_errors_infeasible_1() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    assert_eq!(Err(SolveError::Infeasible),
+               cassowary::optimise(&mut objective_func, &mut subject_to));
+}
+
+#[test]
+fn simplex_test_errors_infeasible_2() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 2.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con2", 5.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    assert_eq!(Err(SolveError::Infeasible),
+               cassowary::optimise(&mut objective_func, &mut subject_to));
+}
+
+#[test]
+fn simplex_test_errors_infeasible_3() {
+    // x has to be 3 but can be no more than 8 / 3, so Phase I stops short of getting W to zero.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 2.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 6.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 3.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 8.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    assert_eq!(Err(SolveError::Infeasible),
+               cassowary::optimise(&mut objective_func, &mut subject_to));
+}
+
+#[test]
+fn simplex_test_errors_malformed_1() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0), new_var("x", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 2.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn simplex_test_errors_malformed_2() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_var("y", 1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn simplex_test_errors_unbounded_1() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", -1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let exp3 = Expression::new(vec![new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 6.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::Unbounded(ray)) => {
+            assert!(ray.contains(&(Name::from("x"), 1.0)));
+            assert!(ray.contains(&(Name::from("sl1"), 1.0)));
+            assert!(!ray.iter().any(|entry| entry.0 == "y"));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn simplex_test_errors_unbounded_2() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", -1.0), new_var("y", -1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", -1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 2.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::Unbounded(ray)) => {
+            assert!(ray.contains(&(Name::from("x"), 1.0)));
+            assert!(ray.contains(&(Name::from("y"), 1.0)));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn simplex_test_errors_bounded_by_surplus_row() {
+    // A GEQ row with a zero RHS keeps x from growing at all, it is not a way out to infinity.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", -1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 0.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(0.0, solution.objective());
+    assert_eq!(Some(0.0), solution.value("x"));
+
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x0", 3.0), new_var("x1", -5.0)]);
+    let exp2 = Expression::new(vec![new_var("x0", -2.0), new_var("x1", -1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 0.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(0.0, solution.objective());
+}
@@ -0,0 +1,116 @@
+extern crate cassowary;
+
+use std::time::Duration;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::options::{Options, CancellationToken};
+use cassowary::errors::SolveError;
+use cassowary::solutions::{Solution, Status};
+
+fn solve_max(options: &Options<f64>) -> Result<Solution<f64>, SolveError<f64>> {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.5), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 3.0), new_var("y", 6.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 90.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 2.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 35.0)]);
+    let exp4 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(new_var("x", 1.0));
+    let c5 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    cassowary::optimise_with(&mut objective_func, &mut subject_to, options)
+}
+
+fn solve_min(options: &Options<f64>) -> Result<Solution<f64>, SolveError<f64>> {
+    // The origin is not feasible so Phase I has to find a starting point.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    cassowary::optimise_with(&mut objective_func, &mut subject_to, options)
+}
+
+#[test]
+fn simplex_test_limits_none_hit() {
+    // Without being asked to, a solve runs until it is done.
+    let mut options = Options::<f64>::default();
+    assert_eq!(None, options.iteration_limit());
+    assert_eq!(None, options.time_limit());
+    options.set_time_limit(Some(Duration::from_secs(60)));
+    let solution = solve_max(&options).unwrap();
+    assert_eq!(Status::Optimal, *solution.status());
+    assert_eq!(55.0, solution.objective());
+    let solution = solve_min(&options).unwrap();
+    assert_eq!(Status::Optimal, *solution.status());
+    assert_eq!(26.0, solution.objective());
+}
+
+#[test]
+fn simplex_test_limits_iteration_limit() {
+    let mut options = Options::default();
+    options.set_iteration_limit(Some(1));
+    let solution = solve_max(&options).unwrap();
+    // The first pivot brings in y as far as the first constraint allows.
+    assert_eq!(Status::IterationLimit, *solution.status());
+    assert_eq!(1, solution.iterations());
+    assert_eq!(45.0, solution.objective());
+    assert_eq!(Some(0.0), solution.value("x"));
+    assert_eq!(Some(15.0), solution.value("y"));
+}
+
+#[test]
+fn simplex_test_limits_time_limit() {
+    let mut options = Options::default();
+    options.set_time_limit(Some(Duration::from_secs(0)));
+    let solution = solve_max(&options).unwrap();
+    assert_eq!(Status::TimeLimit, *solution.status());
+    assert_eq!(0, solution.iterations());
+    assert_eq!(0.0, solution.objective());
+}
+
+#[test]
+fn simplex_test_limits_before_feasible() {
+    let mut options = Options::default();
+    options.set_iteration_limit(Some(0));
+    assert_eq!(Err(SolveError::IterationLimit), solve_min(&options));
+    let mut options = Options::default();
+    options.set_time_limit(Some(Duration::from_secs(0)));
+    assert_eq!(Err(SolveError::TimeLimit), solve_min(&options));
+}
+
+#[test]
+fn simplex_test_limits_cancelled() {
+    let token = CancellationToken::new();
+    let mut options = Options::default();
+    options.set_cancellation_token(Some(token.clone()));
+    assert_eq!(55.0, solve_max(&options).unwrap().objective());
+    token.cancel();
+    let solution = solve_max(&options).unwrap();
+    assert_eq!(Status::Cancelled, *solution.status());
+    assert_eq!(0, solution.iterations());
+    assert_eq!(Err(SolveError::Cancelled), solve_min(&options));
+}
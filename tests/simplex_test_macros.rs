@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate cassowary;
+
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::constraints::Constraint;
+
+#[test]
+fn simplex_test_macros_max_1() {
+    let (mut objective_func, mut subject_to) = lp!(<f32>
+        maximize P = 2.5 x + 3 y;
+        3 x + 6 y <= 90;
+        2 x + y <= 35;
+        x + y <= 20;
+        x, y >= 0
+    );
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(2, solution.values().len());
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.value("y"));
+    assert_eq!(Some(5.0), solution.internal_value("sl2"));
+}
+
+#[test]
+fn simplex_test_macros_max_2() {
+    let (mut objective_func, mut subject_to) = lp!(<f32>
+        maximize Z = 8 x1 + 10 x2 + 7 x3;
+        x1 + 3 x2 + 2 x3 <= 10;
+        x1 + 5 x2 + x3 <= 8;
+        x1, x2, x3 >= 0;
+    );
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3, solution.values().len());
+    assert_eq!(64.0, solution.objective());
+    assert_eq!(Some(8.0), solution.value("x1"));
+    assert_eq!(Some(2.0), solution.internal_value("sl1"));
+}
+
+#[test]
+fn simplex_test_macros_min() {
+    let (mut objective_func, mut subject_to) = lp!(<Rational>
+        minimise C = 2 x + 3 y;
+        x + y >= 10;
+        x - 2 y = -2;
+        x, y >= 0
+    );
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Rational::new(24, 1), solution.objective());
+    assert_eq!(Some(Rational::new(6, 1)), solution.value("x"));
+    assert_eq!(Some(Rational::new(4, 1)), solution.value("y"));
+}
+
+#[test]
+fn simplex_test_macros_constraints() {
+    let subject_to = constraints!(0.5 x1 + 2 x2 <= 24; -x1 - 1.5 x2 >= -30; x1, x2 >= 0);
+    let constraints = subject_to.system();
+    assert_eq!(4, constraints.len());
+    match constraints[1] {
+        Constraint::Regular(ref exp) => {
+            assert_eq!(&vec![new_var("x1", -1.0), new_var("x2", -1.5)], exp.lhs());
+            assert_eq!(&Relationship::GEQ, exp.rel());
+            assert_eq!(&vec![new_const("RHS", -30.0)], exp.rhs());
+        }
+        _ => panic!("Expected a regular constraint."),
+    }
+    match constraints[3] {
+        Constraint::NonNegative(ref var) => assert_eq!(&new_var("x2", 1.0), var),
+        _ => panic!("Expected a non-negativity constraint."),
+    }
+    let expected = Expression::new(vec![new_var("x1", 0.5), new_var("x2", 2.0)],
+                                   Relationship::LEQ,
+                                   vec![new_const("RHS", 24.0)]);
+    match constraints[0] {
+        Constraint::Regular(ref exp) => {
+            assert_eq!((expected.lhs(), expected.rel(), expected.rhs()),
+                       (exp.lhs(), exp.rel(), exp.rhs()))
+        }
+        _ => panic!("Expected a regular constraint."),
+    }
+}
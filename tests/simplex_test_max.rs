@@ -9,7 +9,7 @@ use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfCo
 
 #[test]
 fn simplex_test_max_1() {
-    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+    let exp1 = Expression::new(vec![new_var("P", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x", 2.5), new_var("y", 3.0)]);
     let exp2 = Expression::new(vec![new_var("x", 3.0), new_var("y", 6.0)],
@@ -28,17 +28,17 @@ fn simplex_test_max_1() {
     let c4 = new_non_neg_con(new_var("x", 1.0));
     let c5 = new_non_neg_con(new_var("y", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(4, solution.len());
-    assert!(solution.contains(&("P".to_string(), 55.0)));
-    assert!(solution.contains(&("x".to_string(), 10.0)));
-    assert!(solution.contains(&("y".to_string(), 10.0)));
-    assert!(solution.contains(&("sl2".to_string(), 5.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(2, solution.values().len());
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.value("y"));
+    assert_eq!(Some(5.0), solution.internal_value("sl2"));
 }
 
 #[test]
 fn simplex_test_max_2() {
-    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+    let exp1 = Expression::new(vec![new_var("Z", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 8.0), new_var("x2", 10.0), new_var("x3", 7.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 1.0), new_var("x2", 3.0), new_var("x3", 2.0)],
@@ -54,16 +54,16 @@ fn simplex_test_max_2() {
     let c4 = new_non_neg_con(new_var("x2", 1.0));
     let c5 = new_non_neg_con(new_var("x3", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(3, solution.len());
-    assert!(solution.contains(&("Z".to_string(), 64.0)));
-    assert!(solution.contains(&("x1".to_string(), 8.0)));
-    assert!(solution.contains(&("sl1".to_string(), 2.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3, solution.values().len());
+    assert_eq!(64.0, solution.objective());
+    assert_eq!(Some(8.0), solution.value("x1"));
+    assert_eq!(Some(2.0), solution.internal_value("sl1"));
 }
 
 #[test]
 fn simplex_test_max_3() {
-    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+    let exp1 = Expression::new(vec![new_var("P", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 7.0), new_var("x2", 8.0), new_var("x3", 10.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 2.0), new_var("x2", 3.0), new_var("x3", 2.0)],
@@ -79,16 +79,16 @@ fn simplex_test_max_3() {
     let c4 = new_non_neg_con(new_var("x2", 1.0));
     let c5 = new_non_neg_con(new_var("x3", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(3, solution.len());
-    assert!(solution.contains(&("P".to_string(), 4400.0)));
-    assert!(solution.contains(&("x1".to_string(), 200.0)));
-    assert!(solution.contains(&("x3".to_string(), 300.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3, solution.values().len());
+    assert_eq!(4400.0, solution.objective());
+    assert_eq!(Some(200.0), solution.value("x1"));
+    assert_eq!(Some(300.0), solution.value("x3"));
 }
 
 #[test]
 fn simplex_test_max_4() {
-    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+    let exp1 = Expression::new(vec![new_var("Z", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", -3.0), new_var("x3", 1.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 1.0),
@@ -112,17 +112,17 @@ fn simplex_test_max_4() {
     let c6 = new_non_neg_con(new_var("x3", 1.0));
     let c7 = new_non_neg_con(new_var("x4", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5, c6, c7]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(4, solution.len());
-    assert!(solution.contains(&("Z".to_string(), 1.5)));
-    assert!(solution.contains(&("x2".to_string(), 2.5)));
-    assert!(solution.contains(&("x3".to_string(), 1.5)));
-    assert!(solution.contains(&("x4".to_string(), 0.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(4, solution.values().len());
+    assert_eq!(1.5, solution.objective());
+    assert_eq!(Some(2.5), solution.value("x2"));
+    assert_eq!(Some(1.5), solution.value("x3"));
+    assert_eq!(Some(0.0), solution.value("x4"));
 }
 
 #[test]
 fn simplex_test_max_5() {
-    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+    let exp1 = Expression::new(vec![new_var("P", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 1.0), new_var("x2", -1.0), new_var("x3", 3.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 1.0), new_var("x2", 1.0)],
@@ -142,16 +142,16 @@ fn simplex_test_max_5() {
     let c5 = new_non_neg_con(new_var("x2", 1.0));
     let c6 = new_non_neg_con(new_var("x3", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5, c6]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert!(solution.contains(&("P".to_string(), 10.0)));
-    assert!(solution.contains(&("x2".to_string(), 5.0)));
-    assert!(solution.contains(&("x3".to_string(), 5.0)));
-    assert!(solution.contains(&("sl1".to_string(), 15.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(10.0, solution.objective());
+    assert_eq!(Some(5.0), solution.value("x2"));
+    assert_eq!(Some(5.0), solution.value("x3"));
+    assert_eq!(Some(15.0), solution.internal_value("sl1"));
 }
 
 #[test]
 fn simplex_test_max_6() {
-    let exp1 = Expression::new(vec![new_var("F", 1.0)],
+    let exp1 = Expression::new(vec![new_var("F", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 2.0), new_var("x2", 3.0), new_var("x3", 4.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 3.0), new_var("x2", 1.0), new_var("x3", 4.0)],
@@ -171,9 +171,28 @@ fn simplex_test_max_6() {
     let c5 = new_non_neg_con(new_var("x2", 1.0));
     let c6 = new_non_neg_con(new_var("x3", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5, c6]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert!(solution.contains(&("F".to_string(), 660.0)));
-    assert!(solution.contains(&("x2".to_string(), 60.000004)));
-    assert!(solution.contains(&("x3".to_string(), 120.0)));
-    assert!(solution.contains(&("sl1".to_string(), 59.99998)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(660.0, solution.objective());
+    assert_eq!(Some(60.0), solution.value("x2"));
+    assert_eq!(Some(120.0), solution.value("x3"));
+    assert_eq!(Some(60.0), solution.internal_value("sl1"));
+}
+
+#[test]
+fn simplex_test_max_unit_column_in_equation() {
+    let exp1 = Expression::new(vec![new_var("F", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 7.0)]);
+    let exp3 = Expression::new(vec![new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 3.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2), new_reg_con(exp3)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3.0, solution.objective());
+    assert_eq!(Some(7.0), solution.value("x"));
+    assert_eq!(Some(3.0), solution.value("y"));
 }
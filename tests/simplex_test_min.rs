@@ -6,6 +6,15 @@ use cassowary::math::expressions::Expression;
 use cassowary::objective::functions::Function;
 use cassowary::objective::problems::ProblemType;
 use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::tableau::tolerances::Tolerances;
+
+// Values that take a long run of f32 pivots only come out right to within the tolerance.
+fn assert_within_tolerance(expected: f32, actual: f32) {
+    assert!(Tolerances::default().is_zero(&(actual - expected)),
+            "{} is not within tolerance of {}",
+            actual,
+            expected);
+}
 
 #[test]
 fn simplex_test_min_1() {
@@ -13,7 +22,7 @@ fn simplex_test_min_1() {
     // Minimise the cost but certain dietary requirements have to be satisfied.
     //
     // Minimise 2b + 3.5m + 8c + 1.5p + 11f + y (the cost of the snack)
-    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+    let exp1 = Expression::new(vec![new_var("C", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("b", 2.0),
                                     new_var("m", 3.5),
@@ -78,20 +87,20 @@ fn simplex_test_min_1() {
     let c12 = new_non_neg_con(new_var("y", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5, c6, c7, c8, c9, c10,
                                                        c11, c12]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(7, solution.len());
-    assert!(solution.contains(&("C".to_string(), 9.174414)));
-    assert!(solution.contains(&("m".to_string(), 0.5644002)));
-    assert!(solution.contains(&("c".to_string(), 0.18481092)));
-    assert!(solution.contains(&("p".to_string(), 0.14701743)));
-    assert!(solution.contains(&("f".to_string(), 0.5)));
-    assert!(solution.contains(&("su4".to_string(), 16.57866)));
-    assert!(solution.contains(&("sl5".to_string(), 0.43559977)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(6, solution.values().len());
+    assert_within_tolerance(9.174414, solution.objective());
+    assert_within_tolerance(0.5644002, solution.value("m").unwrap());
+    assert_within_tolerance(0.18481094, solution.value("c").unwrap());
+    assert_within_tolerance(0.14701745, solution.value("p").unwrap());
+    assert_eq!(Some(0.5), solution.value("f"));
+    assert_within_tolerance(16.57866, solution.internal_value("su4").unwrap());
+    assert_within_tolerance(0.4355998, solution.internal_value("sl5").unwrap());
 }
 
 #[test]
 fn simplex_test_min_2() {
-    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+    let exp1 = Expression::new(vec![new_var("P", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x", 4.0), new_var("y", -5.0), new_var("z", -3.0)]);
     let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", -1.0), new_var("z", 1.0)],
@@ -107,16 +116,16 @@ fn simplex_test_min_2() {
     let c4 = new_non_neg_con(new_var("y", 1.0));
     let c5 = new_non_neg_con(new_var("z", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(3, solution.len());
-    assert!(solution.contains(&("P".to_string(), -38.0 / 3.0)));
-    assert!(solution.contains(&("y".to_string(), 7.0 / 3.0)));
-    assert!(solution.contains(&("z".to_string(), 1.0 / 3.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3, solution.values().len());
+    assert_eq!(-38.0 / 3.0, solution.objective());
+    assert_eq!(Some(7.0 / 3.0), solution.value("y"));
+    assert_eq!(Some(1.0 / 3.0), solution.value("z"));
 }
 
 #[test]
 fn simplex_test_min_3() {
-    let exp1 = Expression::new(vec![new_var("W", 1.0)],
+    let exp1 = Expression::new(vec![new_var("W", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("y1", 3.0), new_var("y2", 2.0), new_var("y3", 3.0)]);
     let exp2 = Expression::new(vec![new_var("y1", 2.0), new_var("y2", 3.0), new_var("y3", 6.0)],
@@ -132,16 +141,16 @@ fn simplex_test_min_3() {
     let c4 = new_non_neg_con(new_var("y2", 1.0));
     let c5 = new_non_neg_con(new_var("y3", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(3, solution.len());
-    assert!(solution.contains(&("W".to_string(), 20.0)));
-    assert!(solution.contains(&("y2".to_string(), 10.0)));
-    assert!(solution.contains(&("sl1".to_string(), 30.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(3, solution.values().len());
+    assert_eq!(20.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("y2"));
+    assert_within_tolerance(30.0, solution.internal_value("sl1").unwrap());
 }
 
 #[test]
 fn simplex_test_min_4() {
-    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+    let exp1 = Expression::new(vec![new_var("C", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 3.0), new_var("x2", 9.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 2.0), new_var("x2", 1.0)],
@@ -156,11 +165,11 @@ fn simplex_test_min_4() {
     let c3 = new_non_neg_con(new_var("x1", 1.0));
     let c4 = new_non_neg_con(new_var("x2", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(3, solution.len());
-    assert!(solution.contains(&("C".to_string(), 24.0)));
-    assert!(solution.contains(&("x1".to_string(), 8.0)));
-    assert!(solution.contains(&("su1".to_string(), 8.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(2, solution.values().len());
+    assert_eq!(24.0, solution.objective());
+    assert_eq!(Some(8.0), solution.value("x1"));
+    assert_eq!(Some(8.0), solution.internal_value("su1"));
 }
 
 #[test]
@@ -169,7 +178,7 @@ fn simplex_test_min_5() {
     // Minimise the cost of the drink.
     //
     // Minimise Z = 2x1 + 3x2 (cost of the drink)
-    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+    let exp1 = Expression::new(vec![new_var("Z", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 2.0), new_var("x2", 3.0)]);
     // 0.5x1 + 0.25x2 <= 4 (sugar)
@@ -191,17 +200,17 @@ fn simplex_test_min_5() {
     let c4 = new_non_neg_con(new_var("x1", 1.0));
     let c5 = new_non_neg_con(new_var("x2", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(4, solution.len());
-    assert!(solution.contains(&("Z".to_string(), 25.0)));
-    assert!(solution.contains(&("x1".to_string(), 5.0)));
-    assert!(solution.contains(&("x2".to_string(), 5.0)));
-    assert!(solution.contains(&("sl1".to_string(), 0.25)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(2, solution.values().len());
+    assert_eq!(25.0, solution.objective());
+    assert_within_tolerance(5.0, solution.value("x1").unwrap());
+    assert_within_tolerance(5.0, solution.value("x2").unwrap());
+    assert_eq!(Some(0.25), solution.internal_value("sl1"));
 }
 
 #[test]
 fn simplex_test_min_6() {
-    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+    let exp1 = Expression::new(vec![new_var("Z", 1.0f32)],
                                Relationship::EQ,
                                vec![new_var("x1", 3.0), new_var("x2", 1.0)]);
     let exp2 = Expression::new(vec![new_var("x1", 4.0), new_var("x2", 1.0)],
@@ -220,10 +229,50 @@ fn simplex_test_min_6() {
     let c4 = new_non_neg_con(new_var("x1", 1.0));
     let c5 = new_non_neg_con(new_var("x2", 1.0));
     let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
-    let solution = cassowary::optimise(&mut objective_func, &mut subject_to);
-    assert_eq!(4, solution.len());
-    assert!(solution.contains(&("Z".to_string(), 23.0 / 7.0)));
-    assert!(solution.contains(&("x1".to_string(), 5.0 / 7.0)));
-    assert!(solution.contains(&("x2".to_string(), 8.0 / 7.0)));
-    assert!(solution.contains(&("sl3".to_string(), 22.0 / 14.0)));
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(2, solution.values().len());
+    assert_eq!(23.0 / 7.0, solution.objective());
+    assert_eq!(Some(5.0 / 7.0), solution.value("x1"));
+    assert_eq!(Some(8.0 / 7.0), solution.value("x2"));
+    assert_eq!(Some(22.0 / 14.0), solution.internal_value("sl3"));
+}
+
+#[test]
+fn simplex_test_min_zero_objective() {
+    // x is a unit column of the only row, but the artificial variable of that row has to be the
+    // basic one Phase I starts from.
+    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("RHS", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 7.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(0.0, solution.objective());
+    assert_eq!(Some(7.0), solution.value("x"));
+}
+
+#[test]
+fn simplex_test_min_negative_rhs() {
+    // The second row is not met with every variable at zero, so Phase I has to find a start.
+    let exp1 = Expression::new(vec![new_var("Z", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x0", -4.0), new_var("x1", -2.0), new_var("x2", -2.0)]);
+    let exp2 = Expression::new(vec![new_var("x0", 3.0), new_var("x1", 3.0), new_var("x2", 2.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let exp3 = Expression::new(vec![new_var("x0", 3.0), new_var("x1", -1.0), new_var("x2", -2.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", -4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x0", 1.0));
+    let c4 = new_non_neg_con(new_var("x1", 1.0));
+    let c5 = new_non_neg_con(new_var("x2", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(-4.0, solution.objective());
 }
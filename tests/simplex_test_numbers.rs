@@ -0,0 +1,94 @@
+extern crate cassowary;
+
+use cassowary::Num;
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::solutions::Solution;
+
+fn solve_max_1<N: Num>() -> Solution<N> {
+    let n = N::from_i32;
+    let exp1 = Expression::new(vec![new_var("P", n(1))],
+                               Relationship::EQ,
+                               vec![new_var("x", n(5) / n(2)), new_var("y", n(3))]);
+    let exp2 = Expression::new(vec![new_var("x", n(3)), new_var("y", n(6))],
+                               Relationship::LEQ,
+                               vec![new_const("con1", n(90))]);
+    let exp3 = Expression::new(vec![new_var("x", n(2)), new_var("y", n(1))],
+                               Relationship::LEQ,
+                               vec![new_const("con2", n(35))]);
+    let exp4 = Expression::new(vec![new_var("x", n(1)), new_var("y", n(1))],
+                               Relationship::LEQ,
+                               vec![new_const("con3", n(20))]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(new_var("x", n(1)));
+    let c5 = new_non_neg_con(new_var("y", n(1)));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    cassowary::optimise(&mut objective_func, &mut subject_to).unwrap()
+}
+
+#[test]
+fn simplex_test_numbers_f32() {
+    let solution = solve_max_1::<f32>();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.value("y"));
+    assert_eq!(Some(5.0), solution.internal_value("sl2"));
+}
+
+#[test]
+fn simplex_test_numbers_f64() {
+    let solution = solve_max_1::<f64>();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(10.0), solution.value("y"));
+    assert_eq!(Some(5.0), solution.internal_value("sl2"));
+}
+
+#[test]
+fn simplex_test_numbers_rational() {
+    let solution = solve_max_1::<Rational>();
+    assert_eq!(Rational::from_i32(55), solution.objective());
+    assert_eq!(Some(Rational::from_i32(10)), solution.value("x"));
+    assert_eq!(Some(Rational::from_i32(10)), solution.value("y"));
+    assert_eq!(Some(Rational::from_i32(5)), solution.internal_value("sl2"));
+    assert_eq!(Some(Rational::new(1, 6)), solution.dual(1));
+    assert_eq!(Some(Rational::zero()), solution.dual(2));
+    assert_eq!(Some(Rational::from_i32(2)), solution.dual(3));
+    let range = solution.rhs_range(3).unwrap();
+    assert_eq!(Some(Rational::from_i32(15)), range.lower());
+    assert_eq!(Some(Rational::new(65, 3)), range.upper());
+}
+
+#[test]
+fn simplex_test_numbers_rational_exact_fractions() {
+    let n = Rational::from_i32;
+    let exp1 = Expression::new(vec![new_var("P", n(1))],
+                               Relationship::EQ,
+                               vec![new_var("x", n(1)), new_var("y", n(1))]);
+    let exp2 = Expression::new(vec![new_var("x", n(3)), new_var("y", n(7))],
+                               Relationship::LEQ,
+                               vec![new_const("con1", n(1))]);
+    let exp3 = Expression::new(vec![new_var("x", n(7)), new_var("y", n(3))],
+                               Relationship::LEQ,
+                               vec![new_const("con2", n(1))]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", n(1)));
+    let c4 = new_non_neg_con(new_var("y", n(1)));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Rational::new(1, 5), solution.objective());
+    assert_eq!(Some(Rational::new(1, 10)), solution.value("x"));
+    assert_eq!(Some(Rational::new(1, 10)), solution.value("y"));
+    assert_eq!(Some(Rational::new(1, 10)), solution.dual(1));
+    assert_eq!(Some(Rational::new(1, 10)), solution.dual(2));
+}
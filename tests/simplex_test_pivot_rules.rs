@@ -0,0 +1,191 @@
+extern crate cassowary;
+
+use std::thread;
+use std::time::Duration;
+
+use cassowary::Num;
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::tableau::pivot_rules::{Dantzig, Bland, Lexicographic, SteepestEdge, Devex,
+                                      PartialPricing};
+use cassowary::options::{Options, CancellationToken};
+use cassowary::errors::SolveError;
+use cassowary::solutions::{Solution, Status};
+
+fn solve_beale(options: &Options<Rational>) -> Result<Solution<Rational>, SolveError<Rational>> {
+    // Beale's example: Dantzig's rule with ties broken by the first row cycles through six
+    // degenerate bases forever.
+    let n = Rational::from_i32;
+    let exp1 = Expression::new(vec![new_var("P", n(1))],
+                               Relationship::EQ,
+                               vec![new_var("x4", Rational::new(3, 4)),
+                                    new_var("x5", n(-20)),
+                                    new_var("x6", Rational::new(1, 2)),
+                                    new_var("x7", n(-6))]);
+    let exp2 = Expression::new(vec![new_var("x4", Rational::new(1, 4)),
+                                    new_var("x5", n(-8)),
+                                    new_var("x6", n(-1)),
+                                    new_var("x7", n(9))],
+                               Relationship::LEQ,
+                               vec![new_const("con1", n(0))]);
+    let exp3 = Expression::new(vec![new_var("x4", Rational::new(1, 2)),
+                                    new_var("x5", n(-12)),
+                                    new_var("x6", Rational::new(-1, 2)),
+                                    new_var("x7", n(3))],
+                               Relationship::LEQ,
+                               vec![new_const("con2", n(0))]);
+    let exp4 = Expression::new(vec![new_var("x6", n(1))],
+                               Relationship::LEQ,
+                               vec![new_const("con3", n(1))]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut constraints = vec![new_reg_con(exp2), new_reg_con(exp3), new_reg_con(exp4)];
+    for name in &["x4", "x5", "x6", "x7"] {
+        constraints.push(new_non_neg_con(new_var(name, n(1))));
+    }
+    let mut subject_to = SystemOfConstraints::new(constraints);
+    cassowary::optimise_with(&mut objective_func, &mut subject_to, options)
+}
+
+fn solve_beale_in_phase_1(options: &Options<Rational>)
+                          -> Result<Solution<Rational>, SolveError<Rational>> {
+    // Beale's example once more, but with its function as a row Phase I has to bring up to 1, so
+    // it is Phase I that goes around the six degenerate bases.
+    let n = Rational::from_i32;
+    let beale_terms = || {
+        vec![new_var("x4", Rational::new(3, 4)),
+             new_var("x5", n(-20)),
+             new_var("x6", Rational::new(1, 2)),
+             new_var("x7", n(-6))]
+    };
+    let exp1 = Expression::new(vec![new_var("P", n(1))], Relationship::EQ, beale_terms());
+    let exp2 = Expression::new(vec![new_var("x4", Rational::new(1, 4)),
+                                    new_var("x5", n(-8)),
+                                    new_var("x6", n(-1)),
+                                    new_var("x7", n(9))],
+                               Relationship::LEQ,
+                               vec![new_const("con1", n(0))]);
+    let exp3 = Expression::new(vec![new_var("x4", Rational::new(1, 2)),
+                                    new_var("x5", n(-12)),
+                                    new_var("x6", Rational::new(-1, 2)),
+                                    new_var("x7", n(3))],
+                               Relationship::LEQ,
+                               vec![new_const("con2", n(0))]);
+    let exp4 = Expression::new(vec![new_var("x6", n(1))],
+                               Relationship::LEQ,
+                               vec![new_const("con3", n(1))]);
+    let exp5 = Expression::new(beale_terms(), Relationship::GEQ, vec![new_const("con4", n(1))]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut constraints = vec![new_reg_con(exp2),
+                               new_reg_con(exp3),
+                               new_reg_con(exp4),
+                               new_reg_con(exp5)];
+    for name in &["x4", "x5", "x6", "x7"] {
+        constraints.push(new_non_neg_con(new_var(name, n(1))));
+    }
+    let mut subject_to = SystemOfConstraints::new(constraints);
+    cassowary::optimise_with(&mut objective_func, &mut subject_to, options)
+}
+
+#[test]
+fn simplex_test_pivot_rules_dantzig_cycles() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Dantzig);
+    options.set_degenerate_pivot_limit(None);
+    options.set_iteration_limit(Some(100));
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Status::IterationLimit, *solution.status());
+    assert_eq!(100, solution.iterations());
+    assert_eq!(Rational::from_i32(0), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_dantzig_cancelled() {
+    let token = CancellationToken::new();
+    let worker_token = token.clone();
+    let worker = thread::spawn(move || {
+        let mut options = Options::default();
+        options.set_pivot_rule(Dantzig);
+        options.set_degenerate_pivot_limit(None);
+        options.set_iteration_limit(None);
+        options.set_cancellation_token(Some(worker_token));
+        solve_beale(&options).map(|solution| solution.status().clone())
+    });
+    thread::sleep(Duration::from_millis(50));
+    token.cancel();
+    assert_eq!(Ok(Status::Cancelled), worker.join().unwrap());
+}
+
+#[test]
+fn simplex_test_pivot_rules_switch_to_bland() {
+    let solution = solve_beale(&Options::default()).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+    assert_eq!(Some(Rational::from_i32(1)), solution.value("x4"));
+    assert_eq!(Some(Rational::from_i32(1)), solution.value("x6"));
+}
+
+#[test]
+fn simplex_test_pivot_rules_dantzig_cycles_in_phase_1() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Dantzig);
+    options.set_degenerate_pivot_limit(None);
+    options.set_iteration_limit(Some(100));
+    assert_eq!(Err(SolveError::IterationLimit),
+               solve_beale_in_phase_1(&options).map(|solution| solution.objective()));
+}
+
+#[test]
+fn simplex_test_pivot_rules_switch_to_bland_in_phase_1() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Dantzig);
+    let solution = solve_beale_in_phase_1(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_bland() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Bland);
+    options.set_degenerate_pivot_limit(None);
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_lexicographic() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Lexicographic);
+    options.set_degenerate_pivot_limit(None);
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_steepest_edge() {
+    let mut options = Options::default();
+    options.set_pivot_rule(SteepestEdge);
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_devex() {
+    let mut options = Options::default();
+    options.set_pivot_rule(Devex::new());
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+}
+
+#[test]
+fn simplex_test_pivot_rules_partial_pricing() {
+    let mut options = Options::default();
+    options.set_pivot_rule(PartialPricing::new(2));
+    let solution = solve_beale(&options).unwrap();
+    assert_eq!(Rational::new(5, 4), solution.objective());
+    assert_eq!(Some(Rational::from_i32(1)), solution.value("x4"));
+    assert_eq!(Some(Rational::from_i32(1)), solution.value("x6"));
+}
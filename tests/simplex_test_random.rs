@@ -0,0 +1,238 @@
+extern crate cassowary;
+
+use cassowary::Num;
+use cassowary::math::numbers::Rational;
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{Constraint, new_reg_con, new_bounded_con,
+                                        SystemOfConstraints};
+use cassowary::errors::SolveError;
+
+// Every variable is kept within [0, BOX] so a model is either infeasible or has its optimum at
+// one of its vertices.
+const BOX: i32 = 5;
+
+// A linear congruential generator, so every run checks the same models.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, low: i32, high: i32) -> i32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        low + ((self.0 >> 33) % (high - low + 1) as u64) as i32
+    }
+}
+
+#[derive(Clone)]
+struct Row {
+    coefficients: Vec<i32>,
+    rel: Relationship,
+    rhs: i32,
+}
+
+fn get_random_rows(rng: &mut Lcg, num_vars: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for _ in 0..rng.next(1, 4) {
+        let coefficients = (0..num_vars).map(|_| rng.next(-3, 3)).collect::<Vec<i32>>();
+        let rel = match rng.next(0, 4) {
+            0 => Relationship::EQ,
+            1 | 2 => Relationship::GEQ,
+            _ => Relationship::LEQ,
+        };
+        let rhs = rng.next(-6, 6);
+        if coefficients.iter().any(|&coefficient| coefficient != 0) {
+            rows.push(Row { coefficients, rel, rhs });
+        }
+    }
+    for i in 0..num_vars {
+        rows.push(get_unit_row(num_vars, i, Relationship::LEQ, BOX));
+    }
+    rows
+}
+
+fn get_unit_row(num_vars: usize, var: usize, rel: Relationship, rhs: i32) -> Row {
+    let mut coefficients = vec![0; num_vars];
+    coefficients[var] = 1;
+    Row { coefficients, rel, rhs }
+}
+
+fn get_model(rows: &[Row],
+             objective: &[i32],
+             problem_type: ProblemType,
+             mut constraints: Vec<Constraint<Rational>>)
+             -> (Function<Rational>, SystemOfConstraints<Rational>) {
+    let n = Rational::from_i32;
+    let terms = objective.iter()
+        .enumerate()
+        .map(|(i, &coefficient)| new_var(&format!("x{}", i), n(coefficient)))
+        .collect();
+    let exp1 = Expression::new(vec![new_var("P", n(1))], Relationship::EQ, terms);
+    for (i, row) in rows.iter().enumerate() {
+        let lhs = row.coefficients
+            .iter()
+            .enumerate()
+            .filter(|&(_, &coefficient)| coefficient != 0)
+            .map(|(j, &coefficient)| new_var(&format!("x{}", j), n(coefficient)))
+            .collect();
+        let rhs = vec![new_const(&format!("con{}", i + 1), n(row.rhs))];
+        constraints.push(new_reg_con(Expression::new(lhs, row.rel.clone(), rhs)));
+    }
+    (Function::new(exp1, problem_type), SystemOfConstraints::new(constraints))
+}
+
+fn get_problem_type(maximise: bool) -> ProblemType {
+    if maximise { ProblemType::MAX } else { ProblemType::MIN }
+}
+
+// Solves the rows as equations, if they pin down a single point.
+fn solve_square(rows: &[&Row]) -> Option<Vec<Rational>> {
+    let n = Rational::from_i32;
+    let size = rows.len();
+    let mut matrix = rows.iter()
+        .map(|row| {
+            let mut cells = row.coefficients.iter().map(|&c| n(c)).collect::<Vec<Rational>>();
+            cells.push(n(row.rhs));
+            cells
+        })
+        .collect::<Vec<Vec<Rational>>>();
+    for column in 0..size {
+        let pivot_row = (column..size).find(|&row| matrix[row][column] != Rational::zero())?;
+        matrix.swap(column, pivot_row);
+        let pivot = matrix[column][column].clone();
+        for cell in matrix[column].iter_mut() {
+            *cell = cell.clone() / pivot.clone();
+        }
+        let pivot_cells = matrix[column].clone();
+        for (i, cells) in matrix.iter_mut().enumerate() {
+            let factor = cells[column].clone();
+            if i == column || factor == Rational::zero() {
+                continue;
+            }
+            for (cell, pivot_cell) in cells.iter_mut().zip(&pivot_cells) {
+                *cell -= pivot_cell.clone() * factor.clone();
+            }
+        }
+    }
+    Some(matrix.into_iter().map(|mut row| row.pop().unwrap()).collect())
+}
+
+fn is_satisfied(row: &Row, point: &[Rational]) -> bool {
+    let lhs = row.coefficients
+        .iter()
+        .zip(point)
+        .fold(Rational::zero(),
+              |sum, (&coefficient, value)| sum + Rational::from_i32(coefficient) * value.clone());
+    let rhs = Rational::from_i32(row.rhs);
+    match row.rel {
+        Relationship::LEQ => lhs <= rhs,
+        Relationship::GEQ => lhs >= rhs,
+        Relationship::EQ => lhs == rhs,
+    }
+}
+
+// The best objective over every vertex of the rows, found by trying each choice of them that
+// could meet at one, or None if no point meets them all.
+fn get_best_vertex(rows: &[Row],
+                   objective: &[i32],
+                   problem_type: &ProblemType)
+                   -> Option<Rational> {
+    let num_vars = objective.len();
+    let mut best: Option<Rational> = None;
+    for mask in 0u32..1 << rows.len() {
+        if mask.count_ones() as usize != num_vars {
+            continue;
+        }
+        let chosen = rows.iter()
+            .enumerate()
+            .filter(|&(i, _)| mask & (1 << i) != 0)
+            .map(|(_, row)| row)
+            .collect::<Vec<&Row>>();
+        let point = match solve_square(&chosen) {
+            Some(point) => point,
+            None => continue,
+        };
+        if !rows.iter().all(|row| is_satisfied(row, &point)) {
+            continue;
+        }
+        let value = objective.iter()
+            .zip(&point)
+            .fold(Rational::zero(),
+                  |sum, (&c, value)| sum + Rational::from_i32(c) * value.clone());
+        best = match best {
+            Some(current) => {
+                let improves = match *problem_type {
+                    ProblemType::MAX => value > current,
+                    ProblemType::MIN => value < current,
+                };
+                Some(if improves { value } else { current })
+            }
+            None => Some(value),
+        };
+    }
+    best
+}
+
+#[test]
+fn simplex_test_random_against_vertices() {
+    let mut rng = Lcg(7);
+    for case in 0..500 {
+        let num_vars = rng.next(2, 3) as usize;
+        let rows = get_random_rows(&mut rng, num_vars);
+        let objective = (0..num_vars).map(|_| rng.next(-3, 3)).collect::<Vec<i32>>();
+        let problem_type = get_problem_type(rng.next(0, 1) == 0);
+        let mut vertex_rows = rows.clone();
+        for i in 0..num_vars {
+            vertex_rows.push(get_unit_row(num_vars, i, Relationship::GEQ, 0));
+        }
+        let expected = get_best_vertex(&vertex_rows, &objective, &problem_type);
+        let (mut objective_func, mut subject_to) =
+            get_model(&rows, &objective, problem_type, Vec::new());
+        let result = cassowary::optimise(&mut objective_func, &mut subject_to)
+            .map(|solution| solution.objective());
+        assert_eq!(expected.ok_or(SolveError::Infeasible), result, "case {}", case);
+    }
+}
+
+#[test]
+fn simplex_test_random_bounds_as_rows() {
+    // Bounds have to give the same answer as the rows they stand for.
+    let mut rng = Lcg(11);
+    for case in 0..500 {
+        let num_vars = rng.next(2, 3) as usize;
+        let rows = get_random_rows(&mut rng, num_vars);
+        let objective = (0..num_vars).map(|_| rng.next(-3, 3)).collect::<Vec<i32>>();
+        let maximise = rng.next(0, 1) == 0;
+        let mut bounds = Vec::new();
+        let mut lowest_bound = 0;
+        let mut bound_rows = rows.clone();
+        for i in 0..num_vars {
+            let lower = rng.next(-2, 3);
+            lowest_bound = lowest_bound.min(lower);
+            let upper = if rng.next(0, 2) == 0 { None } else { Some(lower + rng.next(0, 4)) };
+            let n = Rational::from_i32;
+            let var = new_var(&format!("x{}", i), n(1));
+            bounds.push(new_bounded_con(var, n(lower), upper.map(n)));
+            bound_rows.push(get_unit_row(num_vars, i, Relationship::GEQ, lower));
+            if let Some(upper) = upper {
+                bound_rows.push(get_unit_row(num_vars, i, Relationship::LEQ, upper));
+            }
+        }
+        let expected = get_best_vertex(&bound_rows, &objective, &get_problem_type(maximise))
+            .ok_or(SolveError::Infeasible);
+        let (mut objective_func, mut subject_to) =
+            get_model(&rows, &objective, get_problem_type(maximise), bounds);
+        let result = cassowary::optimise(&mut objective_func, &mut subject_to)
+            .map(|solution| solution.objective());
+        assert_eq!(expected, result, "case {}", case);
+        // Without a bound below zero the rows can be given to the solver as they are.
+        if lowest_bound >= 0 {
+            let (mut objective_func, mut subject_to) =
+                get_model(&bound_rows, &objective, get_problem_type(maximise), Vec::new());
+            let result = cassowary::optimise(&mut objective_func, &mut subject_to)
+                .map(|solution| solution.objective());
+            assert_eq!(expected, result, "case {} as rows", case);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::solutions::Range;
+
+fn assert_bound(expected: Option<f64>, actual: Option<f64>) {
+    match (expected, actual) {
+        (Some(expected), Some(actual)) => {
+            assert!((expected - actual).abs() < 1e-9, "{} != {}", expected, actual)
+        }
+        _ => assert_eq!(expected, actual),
+    }
+}
+
+fn assert_range(lower: Option<f64>, upper: Option<f64>, actual: Option<Range<f64>>) {
+    match actual {
+        Some(range) => {
+            assert_bound(lower, range.lower());
+            assert_bound(upper, range.upper());
+        }
+        None => panic!("Missing range, expected {:?} to {:?}.", lower, upper),
+    }
+}
+
+#[test]
+fn simplex_test_ranges_max() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.5), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 3.0), new_var("y", 6.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 90.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 2.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 35.0)]);
+    let exp4 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(new_var("x", 1.0));
+    let c5 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_range(Some(1.5), Some(3.0), solution.cost_range("x"));
+    assert_range(Some(2.5), Some(5.0), solution.cost_range("y"));
+    assert_eq!(None, solution.cost_range("sl1"));
+    assert_range(Some(75.0), Some(120.0), solution.rhs_range(1));
+    assert_range(Some(30.0), None, solution.rhs_range(2));
+    assert_range(Some(15.0), Some(65.0 / 3.0), solution.rhs_range(3));
+    assert_eq!(None, solution.rhs_range(4));
+    assert_eq!(3, solution.rhs_ranges().len());
+}
+
+#[test]
+fn simplex_test_ranges_min() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 4.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0), new_var("y", 3.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con2", 6.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let c4 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(9.0, solution.objective());
+    assert_range(Some(1.0), Some(3.0), solution.cost_range("x"));
+    assert_range(Some(2.0), Some(6.0), solution.cost_range("y"));
+    assert_range(Some(2.0), Some(6.0), solution.rhs_range(1));
+    assert_range(Some(4.0), Some(12.0), solution.rhs_range(2));
+}
+
+#[test]
+fn simplex_test_ranges_non_basic() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 3.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_range(Some(1.0), None, solution.cost_range("x"));
+    assert_range(None, Some(3.0), solution.cost_range("y"));
+    assert_range(Some(0.0), None, solution.rhs_range(1));
+}
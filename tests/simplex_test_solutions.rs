@@ -0,0 +1,143 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{Name, new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::solutions::Status;
+use cassowary::options::Options;
+use cassowary::tableau::tolerances::Tolerances;
+
+#[test]
+fn simplex_test_solutions_1() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.5), new_var("y", 3.0), new_var("z", -1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 3.0), new_var("y", 6.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 90.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 2.0), new_var("y", 1.0), new_var("z", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 35.0)]);
+    let exp4 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(new_var("x", 1.0));
+    let c5 = new_non_neg_con(new_var("y", 1.0));
+    let c6 = new_non_neg_con(new_var("z", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5, c6]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Status::Optimal, *solution.status());
+    assert_eq!("P", solution.objective_name());
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(vec![(Name::from("x"), 10.0), (Name::from("y"), 10.0), (Name::from("z"), 0.0)],
+               *solution.values());
+    assert_eq!(None, solution.value("sl2"));
+    assert_eq!(None, solution.value("P"));
+    assert_eq!(Some(5.0), solution.internal_value("sl2"));
+    assert_eq!(Some(0.0), solution.internal_value("sl1"));
+    assert_eq!(3, solution.internals().len());
+    assert!(solution.iterations() > 0);
+    // Every unit of z forced in costs its own unit of profit and frees nothing.
+    assert_eq!(Some(0.0), solution.reduced_cost("x"));
+    assert_eq!(Some(0.0), solution.reduced_cost("y"));
+    assert_eq!(Some(-1.0), solution.reduced_cost("z"));
+    assert_eq!(None, solution.reduced_cost("sl2"));
+}
+
+#[test]
+fn simplex_test_solutions_names_like_internals() {
+    // Only the solver's own slack variables are internal, whatever the others are called.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("ep3", 2.0), new_var("arti4", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("ep3", 1.0), new_var("arti4", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let exp3 = Expression::new(vec![new_var("ep3", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(5.0, solution.objective());
+    assert_eq!(vec![(Name::from("ep3"), 1.0), (Name::from("arti4"), 3.0)], *solution.values());
+    assert_eq!(Some(0.0), solution.reduced_cost("arti4"));
+    assert_eq!(None, solution.internal_value("ep3"));
+    assert_eq!(2, solution.internals().len());
+}
+
+#[test]
+fn simplex_test_solutions_2() {
+    // Every point on the edge x + y = 4 gives the same profit.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0), new_var("y", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 4.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Status::AlternativeOptima, *solution.status());
+    assert_eq!(4.0, solution.objective());
+    assert_eq!(1, solution.iterations());
+}
+
+#[test]
+fn simplex_test_solutions_3() {
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 2.0), new_var("y", 3.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_non_neg_con(new_var("x", 1.0));
+    let c3 = new_non_neg_con(new_var("y", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(20.0, solution.objective());
+    // y would only be used once it costs less than x.
+    assert_eq!(vec![(Name::from("x"), 0.0), (Name::from("y"), 1.0)],
+               *solution.reduced_costs());
+}
+
+#[test]
+fn simplex_test_solutions_tolerances() {
+    let solve = |options: &Options<f64>| {
+        let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                                   Relationship::EQ,
+                                   vec![new_var("x", 1.0), new_var("y", 0.9999)]);
+        let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("y", 1.0)],
+                                   Relationship::LEQ,
+                                   vec![new_const("con1", 10.0)]);
+        let mut objective_func = Function::new(exp1, ProblemType::MAX);
+        let c1 = new_reg_con(exp2);
+        let c2 = new_non_neg_con(new_var("x", 1.0));
+        let c3 = new_non_neg_con(new_var("y", 1.0));
+        let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+        cassowary::optimise_with(&mut objective_func, &mut subject_to, options).unwrap()
+    };
+    let solution = solve(&Options::default());
+    assert_eq!(Status::Optimal, *solution.status());
+    assert_eq!(Some(10.0), solution.value("x"));
+    // Bringing y in would lose less than the optimality tolerance, which makes it as good.
+    let mut options = Options::default();
+    options.set_tolerances(Tolerances::new(1e-9, 1e-3));
+    let solution = solve(&options);
+    assert_eq!(Status::AlternativeOptima, *solution.status());
+    assert_eq!(Some(10.0), solution.value("x"));
+}
@@ -0,0 +1,123 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{new_var, new_const};
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, new_weighted_con,
+                                        SystemOfConstraints};
+use cassowary::objective::strengths::Strength;
+
+#[test]
+fn simplex_test_strengths_1() {
+    // Two places the left edge of a box would like to be; the stronger wish wins.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0), new_var("width", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("left", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("left", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con2", 20.0)]);
+    let exp4 = Expression::new(vec![new_var("left", 1.0), new_var("width", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con3", 100.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_weighted_con(exp2, Strength::Medium);
+    let c2 = new_weighted_con(exp3, Strength::Strong);
+    let c3 = new_weighted_con(exp4, Strength::Required);
+    let c4 = new_non_neg_con(new_var("left", 1.0));
+    let c5 = new_non_neg_con(new_var("width", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Some(20.0), solution.value("left"));
+    assert_eq!(Some(80.0), solution.value("width"));
+    assert!(solution.internal_value("em1") == Some(10.0) ||
+            solution.internal_value("ep1") == Some(10.0));
+    assert_eq!(10_000.0, solution.objective());
+}
+
+#[test]
+fn simplex_test_strengths_2() {
+    // A required bound can not be given up no matter how strong the competing wish is.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::GEQ,
+                               vec![new_const("con1", 10.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 5.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_weighted_con(exp3, Strength::Strong);
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Some(10.0), solution.value("x"));
+    assert_eq!(Some(5.0), solution.internal_value("ep2"));
+}
+
+#[test]
+fn simplex_test_strengths_3() {
+    // A soft limit is only worth keeping when its weight beats the gain from breaking it.
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 100.0)]);
+    let exp3 = Expression::new(vec![new_var("x", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 30.0)]);
+    let mut objective_func = Function::new(exp1.clone(), ProblemType::MAX);
+    let c1 = new_reg_con(exp2.clone());
+    let c2 = new_weighted_con(exp3.clone(), Strength::Custom(2.0));
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Some(30.0), solution.value("x"));
+    assert_eq!(30.0, solution.objective());
+
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_weighted_con(exp3, Strength::Custom(0.5));
+    let c3 = new_non_neg_con(new_var("x", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Some(100.0), solution.value("x"));
+    assert_eq!(Some(70.0), solution.internal_value("ep2"));
+    assert_eq!(65.0, solution.objective());
+}
+
+#[test]
+fn simplex_test_strengths_weights_add_up() {
+    // Strengths are weights rather than strict priorities: the medium wish to keep the width at
+    // zero costs 2000 units of error for every unit of left, which outweighs the strong wish.
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("left", 0.0), new_var("width", 0.0)]);
+    let exp2 = Expression::new(vec![new_var("width", 1.0), new_var("left", -2000.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 0.0)]);
+    let exp3 = Expression::new(vec![new_var("left", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con2", 10.0)]);
+    let exp4 = Expression::new(vec![new_var("width", 1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con3", 0.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MIN);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_weighted_con(exp3, Strength::Strong);
+    let c3 = new_weighted_con(exp4, Strength::Medium);
+    let c4 = new_non_neg_con(new_var("left", 1.0));
+    let c5 = new_non_neg_con(new_var("width", 1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(Some(0.0), solution.value("left"));
+    assert_eq!(Some(0.0), solution.value("width"));
+    assert_eq!(10_000_000.0, solution.objective());
+}
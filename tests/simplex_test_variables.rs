@@ -0,0 +1,169 @@
+extern crate cassowary;
+
+use cassowary::math::variables::{new_var, new_const, Variables};
+use cassowary::math::linear_exprs::LinearExpr;
+use cassowary::math::relationships::Relationship;
+use cassowary::math::expressions::Expression;
+use cassowary::objective::functions::Function;
+use cassowary::objective::problems::ProblemType;
+use cassowary::objective::constraints::{new_reg_con, new_non_neg_con, SystemOfConstraints};
+use cassowary::objective::strengths::Strength;
+use cassowary::incremental::solvers::Solver;
+use cassowary::options::Options;
+use cassowary::errors::SolveError;
+
+#[test]
+fn simplex_test_variables_max() {
+    let mut variables = Variables::new();
+    let x = variables.add("x");
+    let y = variables.add("y");
+    let exp1 = Expression::new(vec![new_var("P", 1.0f32)],
+                               Relationship::EQ,
+                               vec![x.term(2.5), y.term(3.0)]);
+    let exp2 = Expression::new(vec![x.term(3.0), y.term(6.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 90.0)]);
+    let exp3 = Expression::new(vec![x.term(2.0), y.term(1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con2", 35.0)]);
+    let exp4 = Expression::new(vec![x.term(1.0), y.term(1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con3", 20.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let c1 = new_reg_con(exp2);
+    let c2 = new_reg_con(exp3);
+    let c3 = new_reg_con(exp4);
+    let c4 = new_non_neg_con(x.term(1.0));
+    let c5 = new_non_neg_con(y.term(1.0));
+    let mut subject_to = SystemOfConstraints::new(vec![c1, c2, c3, c4, c5]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value(&x));
+    assert_eq!(Some(10.0), solution.value(&y));
+    assert_eq!(Some(0.0), solution.reduced_cost(&x));
+    let names = solution.values()
+        .iter()
+        .map(|(key, _)| variables.display_name(key))
+        .collect::<Vec<String>>();
+    assert_eq!(vec!["x".to_string(), "y".to_string()], names);
+}
+
+#[test]
+fn simplex_test_variables_operators() {
+    let mut variables = Variables::new();
+    let x = variables.add("x");
+    let y = variables.add("y");
+    let exp1 = (2.5 * x + 3.0 * y).into_expression(Relationship::EQ, LinearExpr::new());
+    let mut objective_func = Function::new(Expression::new(vec![new_var("P", 1.0)],
+                                                           Relationship::EQ,
+                                                           exp1.lhs().clone()),
+                                           ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![(3.0 * x + 6.0 * y).at_most(90.0),
+                                                       (2.0 * x + y).at_most(35.0),
+                                                       (1.0 * x + y).at_most(20.0),
+                                                       x.at_least(0.0),
+                                                       y.at_least(0.0)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(55.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value(&x));
+    assert_eq!(Some(10.0), solution.value(&y));
+
+    let mut objective_func = Function::new(Expression::new(vec![new_var("P", 1.0)],
+                                                           Relationship::EQ,
+                                                           exp1.lhs().clone()),
+                                           ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![(1.0 * x).equals(4.0),
+                                                       (1.0 * x + 1.0).at_least(y - 5.0),
+                                                       y.at_least(0.0)]);
+    let solution = cassowary::optimise(&mut objective_func, &mut subject_to).unwrap();
+    assert_eq!(40.0, solution.objective());
+    assert_eq!(Some(10.0), solution.value(&y));
+}
+
+#[test]
+fn simplex_test_variables_edit() {
+    let mut variables = Variables::new();
+    let left = variables.add_unnamed();
+    let exp1 = Expression::new(vec![new_var("C", 1.0)],
+                               Relationship::EQ,
+                               vec![left.term(0.0)]);
+    let function = Function::new(exp1, ProblemType::MIN);
+    let mut solver = Solver::with_variables(function, variables, &Options::default()).unwrap();
+    let width = solver.add_var("width");
+    assert_eq!(Some("width"), solver.variables().name(width));
+    let exp2 = Expression::new(vec![left.term(1.0), width.term(1.0)],
+                               Relationship::EQ,
+                               vec![new_const("con1", 100.0)]);
+    solver.add_constraint(new_reg_con(exp2)).unwrap();
+    solver.add_edit_var(&left, Strength::Strong).unwrap();
+    assert!(solver.has_edit_var(&left));
+    solver.suggest_value(&left, 30.0).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(30.0), solution.value(&left));
+    assert_eq!(Some(70.0), solution.value(&width));
+}
+
+#[test]
+fn simplex_test_variables_from_other_registries() {
+    // Both registries hand out a first handle, which stands for a different variable in each.
+    let mut variables = Variables::new();
+    let x = variables.add("x");
+    let y = Variables::new().add("y");
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![x.term(1.0), y.term(1.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![(1.0 * x + y).at_most(10.0)]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("z", 1.0)]);
+    let mut solver = Solver::new(Function::new(exp1, ProblemType::MIN)).unwrap();
+    let z = solver.add_var("z");
+    assert!(z != x);
+    match solver.add_constraint(x.at_most(10.0)) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    match solver.add_edit_var(&x, Strength::Strong) {
+        Err(SolveError::MalformedModel(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    solver.add_constraint(z.at_most(10.0)).unwrap();
+    let solution = solver.solution().unwrap();
+    assert_eq!(Some(0.0), solution.value(&z));
+    assert_eq!(None, solution.value(&x));
+}
+
+#[test]
+fn simplex_test_variables_named_like_handles() {
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("x", 1.0)]);
+    let exp2 = Expression::new(vec![new_var("x", 1.0), new_var("v#0", 1.0)],
+                               Relationship::LEQ,
+                               vec![new_const("con1", 10.0)]);
+    let mut objective_func = Function::new(exp1, ProblemType::MAX);
+    let mut subject_to = SystemOfConstraints::new(vec![new_reg_con(exp2)]);
+    match cassowary::optimise(&mut objective_func, &mut subject_to) {
+        Err(SolveError::MalformedModel(reason)) => {
+            assert_eq!("constraint 1 has a variable named \"v#0\", which reads like a variable \
+                        handle.",
+                       reason)
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let exp1 = Expression::new(vec![new_var("P", 1.0)],
+                               Relationship::EQ,
+                               vec![new_var("v#1", 1.0)]);
+    match Solver::new(Function::new(exp1, ProblemType::MAX)) {
+        Err(SolveError::MalformedModel(_)) => {}
+        Ok(_) => panic!("Unexpected result: a solver."),
+        Err(other) => panic!("Unexpected result: {:?}", other),
+    }
+}